@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use crate::bot::{legales, determinizar};
+use crate::entorno::{elegir_actor};
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::historial::{Historial};
+use crate::jugada::{IJugada, IJugadaId, Jugada};
+use crate::partida::{Partida};
+
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigAnalisis {
+  // rollouts por cada jugada posible en cada decision
+  pub rollouts: usize,
+  // perdida esperada (en puntos) a partir de la cual se marca un fallo
+  pub umbral: f64,
+  pub seed: u64,
+}
+
+impl Default for ConfigAnalisis {
+  fn default() -> ConfigAnalisis {
+    ConfigAnalisis {
+      rollouts: 100,
+      umbral: 1.0,
+      seed: 0,
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum TipoFallo {
+  // no quiso un truco (o un envite) que convenia querer
+  NoQuiso,
+  // no canto un envite (o la flor) que convenia cantar
+  EnviteNoCantado,
+  // tiro una carta peor que otra
+  MalaCarta,
+  Otro,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Fallo {
+  // indice de la jugada en `Historial.jugadas`
+  pub ix: usize,
+  pub jugador: String,
+  pub tipo: TipoFallo,
+  // la jugada hecha y la mejor segun los rollouts, en formato de `cmd`
+  pub jugada: String,
+  pub mejor: String,
+  // puntos esperados que se pierden por no hacer `mejor`
+  pub perdida: f64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Analisis {
+  // jugadas analizadas (las que tenian alguna alternativa)
+  pub decisiones: usize,
+  // los fallos de cada jugador, en el orden en que los hizo
+  pub fallos: HashMap<String, Vec<Fallo>>,
+}
+
+// repasa las jugadas de `historial` y compara cada una con las demas que
+// podia hacer el jugador: para cada opcion juega rollouts al azar hasta el
+// final de la ronda desde lo que el jugador podia ver (repartiendo al azar
+// las cartas que no veia) y se queda con la diferencia de puntos promedio.
+// Las opciones comparten las mismas determinizaciones y numeros al azar.
+pub fn analizar(historial: &Historial, config: &ConfigAnalisis) -> Result<Analisis, Error> {
+  let mut p = Partida::desde_historial(historial)?;
+  p.verbose = false;
+  let mut res = Analisis {
+    decisiones: 0,
+    fallos: historial.azules
+      .iter()
+      .chain(historial.rojos.iter())
+      .map(|jid| (jid.clone(), Vec::new()))
+      .collect(),
+  };
+
+  for (ix, cmd) in historial.jugadas.iter().enumerate() {
+    let tomada = p.parse(cmd)?.jugada();
+    let mix = tomada.jid();
+    let jid = p.ronda.nombre(mix).to_string();
+    let opciones = legales(&p, mix, true);
+    if opciones.len() > 1 && config.rollouts > 0 {
+      res.decisiones += 1;
+      let valores = evaluar(&p, &jid, &opciones, config, ix)?;
+      let mejor = (0..opciones.len())
+        .max_by(|a, b| valores[*a].total_cmp(&valores[*b]))
+        .unwrap();
+      if let Some(t) = opciones.iter().position(|j| *j == tomada) {
+        let perdida = valores[mejor] - valores[t];
+        if perdida > config.umbral {
+          let fallo = Fallo {
+            ix,
+            jugador: jid.clone(),
+            tipo: tipo_de_fallo(&tomada, &opciones[mejor]),
+            jugada: p.comando(&tomada),
+            mejor: p.comando(&opciones[mejor]),
+            perdida,
+          };
+          res.fallos.entry(jid).or_default().push(fallo);
+        }
+      }
+    }
+    p.cmd(cmd)?;
+  }
+
+  Ok(res)
+}
+
+// el valor esperado de cada opcion para el equipo de `jid`
+fn evaluar(
+  p: &Partida,
+  jid: &str,
+  opciones: &[Jugada],
+  config: &ConfigAnalisis,
+  ix: usize,
+) -> Result<Vec<f64>, Error> {
+  let vista = p.perspectiva(jid)?;
+  let equipo = vista.ronda.manojos[vista.ronda.get_mix(jid)?].jugador.equipo;
+  let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(ix as u64));
+  let mut totales = vec![0.0; opciones.len()];
+  for _ in 0..config.rollouts {
+    let d = determinizar(&vista, equipo, &mut rng);
+    let semilla = rng.gen::<u64>();
+    for (total, j) in totales.iter_mut().zip(opciones) {
+      let mut q = d.clone();
+      *total += rollout(&mut q, j, equipo, &mut StdRng::seed_from_u64(semilla));
+    }
+  }
+  Ok(totales.into_iter().map(|t| t / config.rollouts as f64).collect())
+}
+
+// hace `j` y sigue al azar hasta que termina la ronda; retorna los puntos
+// que hizo `equipo` menos los que hizo el contrario
+fn rollout(d: &mut Partida, j: &Jugada, equipo: Equipo, rng: &mut StdRng) -> f64 {
+  let antes = d.puntajes.clone();
+  let numero = d.ronda.numero;
+  let en_juego = |d: &Partida| d.ronda.numero == numero && !d.terminada();
+  j.hacer(d);
+  while en_juego(d) {
+    let Ok(mix) = elegir_actor(d, false).or_else(|_| elegir_actor(d, true)) else { break };
+    let mut opciones = legales(d, mix, false);
+    if opciones.is_empty() {
+      opciones = legales(d, mix, true);
+    }
+    let Some(j) = opciones.choose(rng) else { break };
+    j.hacer(d);
+  }
+  let ganados = |e: Equipo| d.puntajes[&e].saturating_sub(antes[&e]) as f64;
+  ganados(equipo) - ganados(equipo.equipo_contrario())
+}
+
+fn tipo_de_fallo(tomada: &Jugada, mejor: &Jugada) -> TipoFallo {
+  let envites = [
+    IJugadaId::JIdEnvido,
+    IJugadaId::JIdRealEnvido,
+    IJugadaId::JIdFaltaEnvido,
+    IJugadaId::JIdFlor,
+    IJugadaId::JIdContraFlor,
+    IJugadaId::JIdContraFlorAlResto,
+  ];
+  match (tomada.id(), mejor.id()) {
+    (IJugadaId::JIdNoQuiero, _) => TipoFallo::NoQuiso,
+    (t, m) if envites.contains(&m) && !envites.contains(&t) => TipoFallo::EnviteNoCantado,
+    (IJugadaId::JIdTirarCarta, IJugadaId::JIdTirarCarta) => TipoFallo::MalaCarta,
+    _ => TipoFallo::Otro,
+  }
+}
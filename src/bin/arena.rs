@@ -0,0 +1,144 @@
+use truco::{*};
+use std::env;
+use std::process;
+
+// enfrenta 2 configuraciones de bots en muchas partidas y reporta el
+// porcentaje de victorias (con intervalo de confianza de Wilson al 95%) y
+// el promedio de puntos por ronda de cada una.
+//
+// uso: arena <bot> <bot> [--partidas N] [--seed S] [--jugadores 1|2|3]
+//            [--puntuacion P]
+// bots: aleatorio | heuristico | ismcts:<iteraciones>
+
+const USO: &str = "uso: arena <bot> <bot> [--partidas N] [--seed S] \
+  [--jugadores 1|2|3] [--puntuacion P]\n\
+  bots: aleatorio | heuristico | ismcts:<iteraciones>";
+
+struct Config {
+  bots: [String; 2],
+  partidas: usize,
+  seed: u64,
+  jugadores: Vec<usize>,
+  puntuacion: usize,
+}
+
+fn salir(msg: &str) -> ! {
+  eprintln!("{}\n{}", msg, USO);
+  process::exit(1)
+}
+
+fn parse_args() -> Config {
+  let args = env::args().skip(1).collect::<Vec<String>>();
+  let mut bots = Vec::new();
+  let mut config = Config {
+    bots: Default::default(),
+    partidas: 100,
+    seed: 0,
+    jugadores: vec![1, 2, 3],
+    puntuacion: 30,
+  };
+  let mut it = args.iter();
+  while let Some(arg) = it.next() {
+    let mut valor = || it.next()
+      .and_then(|v| v.parse::<u64>().ok())
+      .unwrap_or_else(|| salir(&format!("falta un numero despues de `{}`", arg)));
+    match arg.as_str() {
+      "--partidas" => config.partidas = valor() as usize,
+      "--seed" => config.seed = valor(),
+      "--jugadores" => config.jugadores = vec![valor() as usize],
+      "--puntuacion" => config.puntuacion = valor() as usize,
+      "-h" | "--help" => salir(""),
+      _ => bots.push(arg.clone()),
+    }
+  }
+  if bots.len() != 2 {
+    salir("hay que pasar exactamente 2 bots");
+  }
+  for b in bots.iter() {
+    crear_bot(b, 0);
+  }
+  if config.jugadores.iter().any(|n| !(1..=3).contains(n)) {
+    salir("--jugadores tiene que ser 1, 2 o 3");
+  }
+  config.bots = [bots[0].clone(), bots[1].clone()];
+  config
+}
+
+fn crear_bot(spec: &str, seed: u64) -> Box<dyn Bot> {
+  match spec.split_once(':') {
+    None if spec == "aleatorio" => Box::new(Aleatorio::new(seed)),
+    None if spec == "heuristico" => Box::new(Heuristico::new()),
+    Some(("ismcts", it)) => match it.parse() {
+      Ok(it) => Box::new(Ismcts::new(it, seed)),
+      Err(_) => salir(&format!("iteraciones invalidas `{}`", it)),
+    },
+    _ => salir(&format!("bot desconocido `{}`", spec)),
+  }
+}
+
+// intervalo de confianza de Wilson al 95% para `k` exitos en `n` intentos
+fn wilson(k: usize, n: usize) -> (f64, f64) {
+  if n == 0 {
+    return (0.0, 1.0)
+  }
+  let z = 1.96;
+  let (k, n) = (k as f64, n as f64);
+  let p = k / n;
+  let centro = (p + z * z / (2.0 * n)) / (1.0 + z * z / n);
+  let margen = z / (1.0 + z * z / n) * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+  (centro - margen, centro + margen)
+}
+
+fn main() {
+  let config = parse_args();
+  let nombres = |equipo: &str, n: usize| (0..n)
+    .map(|i| format!("{}{}", equipo, i))
+    .collect::<Vec<String>>();
+
+  for &n in config.jugadores.iter() {
+    let mut ganadas = [0, 0];
+    let mut puntos = [0, 0];
+    let mut rondas = 0;
+    for i in 0..config.partidas {
+      let seed = config.seed + i as u64;
+      let mut p = match Partida::new_with_rng(
+        config.puntuacion,
+        nombres("azul", n),
+        nombres("rojo", n),
+        false,
+        Some(rand::SeedableRng::seed_from_u64(seed)),
+      ) {
+        Ok(p) => p,
+        Err(e) => salir(&e.to_string()),
+      };
+      // se alternan los colores para no favorecer a ninguno
+      let (a, b) = if i % 2 == 0 {(0, 1)} else {(1, 0)};
+      let mut bots = [
+        crear_bot(&config.bots[a], seed),
+        crear_bot(&config.bots[b], seed.wrapping_add(1)),
+      ];
+      if let Err(e) = jugar_partida(&mut p, &mut bots) {
+        salir(&format!("partida {}: {}", seed, e));
+      }
+      let pts = [p.puntajes[&Equipo::Azul], p.puntajes[&Equipo::Rojo]];
+      let ganador = if pts[0] >= p.puntuacion {0} else {1};
+      ganadas[[a, b][ganador]] += 1;
+      puntos[a] += pts[0];
+      puntos[b] += pts[1];
+      rondas += p.historial.repartos.len();
+    }
+
+    println!("{}v{} ({} partidas)", n, n, config.partidas);
+    for ix in 0..2 {
+      let (lo, hi) = wilson(ganadas[ix], config.partidas);
+      println!(
+        "  {:<16} gano {:>5.1}% [{:.1}%, {:.1}%]  {:.2} puntos por ronda",
+        config.bots[ix],
+        100.0 * ganadas[ix] as f64 / config.partidas.max(1) as f64,
+        100.0 * lo,
+        100.0 * hi,
+        puntos[ix] as f64 / rondas.max(1) as f64,
+      );
+    }
+  }
+}
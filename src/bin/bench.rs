@@ -1,8 +1,14 @@
 use truco::{*};
+use std::env;
 use std::time::{Duration, Instant};
 
+// uso: bench [segundos] (por defecto 10 minutos)
 fn main() {
-  let timeout = Duration::from_secs(60 * 10);
+  let segundos = env::args()
+    .nth(1)
+    .and_then(|s| s.parse::<u64>().ok())
+    .unwrap_or(60 * 10);
+  let timeout = Duration::from_secs(segundos);
   let start_time = Instant::now();
   let mut c: usize = 0;
 
@@ -13,7 +19,7 @@ fn main() {
       vec!["bob".to_string()],
       false,
     ).unwrap();
-  
+
     while !p.terminada() {
       let a = random_action(&p, false);
       let _ = a.hacer(&mut p);
@@ -23,4 +29,4 @@ fn main() {
   }
 
   println!("timeout: {:?} -> count: {}", timeout, c);
-}
\ No newline at end of file
+}
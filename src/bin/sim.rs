@@ -0,0 +1,77 @@
+use truco::{*};
+use std::env;
+use std::process;
+use std::time::Instant;
+
+// simula partidas con jugadas al azar en varios hilos y reporta
+// estadisticas agregadas; con la misma semilla el resultado es el mismo
+// sin importar la cantidad de hilos.
+//
+// uso: sim [--hilos H] [--partidas N] [--seed S] [--jugadores 1|2|3]
+//          [--puntuacion P] [--sin-flor] [--sin-contraflor-al-resto]
+//          [--pica-pica] [--mazo]
+
+const USO: &str = "uso: sim [--hilos H] [--partidas N] [--seed S] \
+  [--jugadores 1|2|3] [--puntuacion P] [--sin-flor] \
+  [--sin-contraflor-al-resto] [--pica-pica] [--mazo]";
+
+fn salir(msg: &str) -> ! {
+  eprintln!("{}\n{}", msg, USO);
+  process::exit(1)
+}
+
+fn parse_args() -> ConfigSim {
+  let args = env::args().skip(1).collect::<Vec<String>>();
+  let mut config = ConfigSim::default();
+  let mut it = args.iter();
+  while let Some(arg) = it.next() {
+    let mut valor = || it.next()
+      .and_then(|v| v.parse::<u64>().ok())
+      .unwrap_or_else(|| salir(&format!("falta un numero despues de `{}`", arg)));
+    match arg.as_str() {
+      "--hilos" => config.hilos = valor() as usize,
+      "--partidas" => config.partidas = valor() as usize,
+      "--seed" => config.seed = valor(),
+      "--jugadores" => config.jugadores_por_equipo = valor() as usize,
+      "--puntuacion" => config.puntuacion = valor() as usize,
+      "--sin-flor" => config.reglas.con_flor = false,
+      "--sin-contraflor-al-resto" => config.reglas.contraflor_al_resto = false,
+      "--pica-pica" => config.reglas.pica_pica = true,
+      "--mazo" => config.allow_mazo = true,
+      "-h" | "--help" => salir(""),
+      _ => salir(&format!("argumento desconocido `{}`", arg)),
+    }
+  }
+  config
+}
+
+fn main() {
+  let config = parse_args();
+  let start_time = Instant::now();
+  let res = match simular(&config) {
+    Ok(res) => res,
+    Err(e) => salir(&e.to_string()),
+  };
+  let elapsed = start_time.elapsed();
+
+  println!(
+    "{} partidas {}v{} en {} hilos: {:?} ({:.0} partidas/s)",
+    res.partidas,
+    config.jugadores_por_equipo,
+    config.jugadores_por_equipo,
+    config.hilos,
+    elapsed,
+    res.partidas as f64 / elapsed.as_secs_f64(),
+  );
+  println!("rondas por partida: {:.2}", res.rondas_por_partida());
+  println!("jugadas: {}  paquetes: {}", res.jugadas, res.paquetes);
+  for e in [Equipo::Azul, Equipo::Rojo] {
+    println!("ganadas por {}: {}", e, res.ganadas.get(&e).unwrap_or(&0));
+  }
+  let mut razones = res.razones.iter().collect::<Vec<_>>();
+  razones.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.to_string().cmp(&b.0.to_string())));
+  println!("razones:");
+  for (razon, n) in razones {
+    println!("  {:<28} {}", razon.to_string(), n);
+  }
+}
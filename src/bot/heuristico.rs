@@ -0,0 +1,241 @@
+use crate::envite::{EstadoEnvite};
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::jugada::{*};
+use crate::manojo::{Manojo};
+use crate::partida::{Partida};
+use crate::truco::{EstadoTruco};
+use super::{legales};
+
+// bot por reglas fijas, sin azar: canta segun el envido/la flor que tiene,
+// grita segun el poder de sus cartas y tira la carta mas baja que gana la
+// mano. Sirve de rival para principiantes y de referencia para otros bots.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Heuristico {}
+
+// umbrales (con `calc_poder`: piezas 30..=34, matas 20..=23, chicas < 20)
+const ENVIDO_QUIERO: usize = 27;
+const ENVIDO_TOCAR: usize = 28;
+const ENVIDO_REAL: usize = 31;
+const FLOR_CONTRA: isize = 32;
+const FLOR_AL_RESTO: isize = 35;
+const PODER_TRUCO: usize = 21;
+const PODER_RETRUCO: usize = 30;
+const PODER_VALE4: usize = 32;
+// en malas se pierde poco y se grita con menos; en buenas, si el rival esta
+// en malas, hay que cuidar la ventaja
+const PODER_MALAS: usize = 2;
+const PODER_BUENAS: usize = 1;
+
+impl Heuristico {
+  pub fn new() -> Heuristico {
+    Heuristico {}
+  }
+
+  pub fn elegir(&self, p:&Partida, jid:&str) -> Result<Jugada, Error> {
+    self.elegir_con_razon(p, jid).map(|(j, _)| j)
+  }
+
+  // como `elegir` pero ademas dice por que
+  pub fn elegir_con_razon(&self, p:&Partida, jid:&str) -> Result<(Jugada, &'static str), Error> {
+    let mix = p.ronda.get_mix(jid)?;
+    let mut opciones = legales(p, mix, false);
+    if opciones.is_empty() {
+      opciones = legales(p, mix, true);
+    }
+    if opciones.is_empty() {
+      return Err(Error::JugadaIlegal(format!("{} no tiene jugadas validas", jid)))
+    }
+    let puede = |id: IJugadaId| opciones.iter().find(|j| j.id() == id).cloned();
+
+    // en pica-pica la ronda que importa es la del duelo
+    let q = p.get_duelo(jid).unwrap_or(p);
+    let r = &q.ronda;
+    let m = r.get_manojo(jid)?;
+    let equipo = m.jugador.equipo;
+    let muestra = &r.muestra;
+    let flor = if r.tiene_flor(m).0 {m.calc_flor(muestra)} else {-1};
+    let envido = if flor < 0 {m.calcular_envido(muestra)} else {0};
+    let es_rival = |jid: Option<usize>| r.equipo_de(jid).is_some_and(|e| e != equipo);
+    // si va perdiendo se arriesga mas
+    let ajuste = if q.puntajes[&equipo] < q.puntajes[&equipo.equipo_contrario()] {1} else {0};
+    let fuerza = fuerza(q, m);
+    let umbrales = umbrales_truco(q, equipo);
+
+    // flor
+    if let Some(j) = puede(IJugadaId::JIdFlor) {
+      return Ok((j, "tiene flor"))
+    }
+    let flor_cantada = [EstadoEnvite::Flor, EstadoEnvite::ContraFlor, EstadoEnvite::ContraFlorAlResto]
+      .contains(&r.envite.estado);
+    if flor >= 0 && flor_cantada && es_rival(r.envite.cantado_por) {
+      if flor >= FLOR_AL_RESTO {
+        if let Some(j) = puede(IJugadaId::JIdContraFlorAlResto) {
+          return Ok((j, "flor muy alta"))
+        }
+      }
+      if flor >= FLOR_CONTRA {
+        if let Some(j) = puede(IJugadaId::JIdContraFlor) {
+          return Ok((j, "flor alta"))
+        }
+        if let Some(j) = puede(IJugadaId::JIdQuiero) {
+          return Ok((j, "flor alta"))
+        }
+      }
+      if let Some(j) = puede(IJugadaId::JIdNoQuiero) {
+        return Ok((j, "flor baja"))
+      }
+    }
+
+    // responder un envite
+    let envite_cantado = [EstadoEnvite::Envido, EstadoEnvite::RealEnvido, EstadoEnvite::FaltaEnvido]
+      .contains(&r.envite.estado);
+    if envite_cantado && es_rival(r.envite.cantado_por) && puede(IJugadaId::JIdQuiero).is_some() {
+      if envido >= ENVIDO_REAL && r.envite.estado == EstadoEnvite::Envido {
+        if let Some(j) = puede(IJugadaId::JIdRealEnvido) {
+          return Ok((j, "envido muy alto"))
+        }
+      }
+      let minimo = match r.envite.estado {
+        // a la falta solo con mucho, salvo que se este en malas
+        EstadoEnvite::FaltaEnvido if !q.esta_en_malas(equipo) => ENVIDO_REAL + 1,
+        EstadoEnvite::RealEnvido => ENVIDO_TOCAR,
+        _ => ENVIDO_QUIERO,
+      };
+      if envido + ajuste >= minimo {
+        return Ok((puede(IJugadaId::JIdQuiero).unwrap(), "envido alto"))
+      }
+      if let Some(j) = puede(IJugadaId::JIdNoQuiero) {
+        return Ok((j, "envido bajo"))
+      }
+    }
+
+    // responder un truco
+    let truco_cantado = [EstadoTruco::Truco, EstadoTruco::ReTruco, EstadoTruco::Vale4]
+      .contains(&r.truco.estado);
+    if truco_cantado && es_rival(r.truco.cantado_por) && puede(IJugadaId::JIdQuiero).is_some() {
+      // el envido esta primero
+      if envido >= ENVIDO_TOCAR {
+        if let Some(j) = puede(IJugadaId::JIdEnvido) {
+          return Ok((j, "el envido esta primero"))
+        }
+      }
+      if let Some(j) = gritar(&puede, fuerza + ajuste, umbrales) {
+        return Ok((j, "cartas para subir el truco"))
+      }
+      let [truco, retruco, vale4] = umbrales;
+      let minimo = match r.truco.estado {
+        EstadoTruco::Truco => truco,
+        EstadoTruco::ReTruco => retruco - 3,
+        _ => vale4 - 3,
+      };
+      if fuerza + ajuste >= minimo {
+        return Ok((puede(IJugadaId::JIdQuiero).unwrap(), "cartas para querer"))
+      }
+      if let Some(j) = puede(IJugadaId::JIdNoQuiero) {
+        return Ok((j, "cartas bajas"))
+      }
+    }
+
+    // tocar envido
+    if envido + ajuste >= ENVIDO_REAL {
+      if let Some(j) = puede(IJugadaId::JIdRealEnvido) {
+        return Ok((j, "envido muy alto"))
+      }
+    }
+    if envido + ajuste >= ENVIDO_TOCAR {
+      if let Some(j) = puede(IJugadaId::JIdEnvido) {
+        return Ok((j, "envido alto"))
+      }
+    }
+
+    // gritar
+    if let Some(j) = gritar(&puede, fuerza + ajuste, umbrales) {
+      return Ok((j, "cartas altas"))
+    }
+
+    // tirar la carta mas baja que gana la mano, o la mas baja si no puede
+    // ganarla o si ya la esta ganando uno de su equipo
+    let mut cartas = opciones
+      .iter()
+      .filter_map(|j| match j {
+        Jugada::TirarCarta(t) => Some((t.carta.calc_poder(muestra), j.clone())),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    cartas.sort_by_key(|(poder, _)| *poder);
+    if !cartas.is_empty() {
+      let mano = &r.manos[r.mano_en_juego as usize];
+      let mejor = |de_rival: bool| mano.cartas_tiradas
+        .iter()
+        .filter(|t| es_rival(Some(t.jugador)) == de_rival)
+        .map(|t| t.carta.calc_poder(muestra))
+        .max();
+      let rival = mejor(true);
+      let propio = mejor(false);
+      let la_gana_el_equipo = propio.is_some() && propio > rival;
+      if !la_gana_el_equipo {
+        if let Some((_, j)) = cartas.iter().find(|(poder, _)| Some(*poder) > rival) {
+          return Ok((j.clone(), "la carta mas baja que gana"))
+        }
+      }
+      return Ok((cartas.remove(0).1, "la carta mas baja"))
+    }
+
+    if let Some(j) = puede(IJugadaId::JIdQuiero) {
+      return Ok((j, "no hay otra"))
+    }
+    Ok((opciones.remove(0), "no hay otra"))
+  }
+}
+
+// la fuerza minima para el truco, el retruco y el vale 4 segun si `equipo`
+// esta en malas o en buenas
+fn umbrales_truco(p:&Partida, equipo:Equipo) -> [usize; 3] {
+  let base = [PODER_TRUCO, PODER_RETRUCO, PODER_VALE4];
+  if p.esta_en_malas(equipo) {
+    base.map(|u| u - PODER_MALAS)
+  } else if p.esta_en_malas(equipo.equipo_contrario()) {
+    base.map(|u| u + PODER_BUENAS)
+  } else {
+    base
+  }
+}
+
+// grita el truco, el retruco o el vale 4 (el que se pueda) si le da la fuerza
+fn gritar<F>(puede: &F, fuerza: usize, umbrales: [usize; 3]) -> Option<Jugada>
+  where F: Fn(IJugadaId) -> Option<Jugada>
+{
+  let [truco, retruco, vale4] = umbrales;
+  [
+    (IJugadaId::JIdVale4, vale4),
+    (IJugadaId::JIdReTruco, retruco),
+    (IJugadaId::JIdTruco, truco),
+  ]
+    .iter()
+    .filter(|(_, minimo)| fuerza >= *minimo)
+    .find_map(|(id, _)| puede(*id))
+}
+
+// el poder de la mejor carta que le queda, con un plus por cada mano que
+// ya gano su equipo
+fn fuerza(p:&Partida, m:&Manojo) -> usize {
+  let muestra = &p.ronda.muestra;
+  let mejor = m.cartas
+    .iter()
+    .zip(m.tiradas)
+    .filter(|(_, tirada)| !tirada)
+    .map(|(c, _)| c.calc_poder(muestra))
+    .max()
+    .unwrap_or(0);
+  let ganador = if m.jugador.equipo == Equipo::Azul {
+    crate::Resultado::GanoAzul
+  } else {
+    crate::Resultado::GanoRojo
+  };
+  let ganadas = p.ronda.manos
+    .iter()
+    .filter(|mano| mano.resultado == ganador)
+    .count();
+  mejor + 5 * ganadas
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use crate::carta::{Carta, carta_from_id};
+use crate::entorno::{elegir_actor};
+use crate::envite::{EstadoEnvite};
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::jugada::{IJugada, Jugada};
+use crate::mano::{NumMano};
+use crate::partida::{Partida};
+use crate::ronda::{Ronda};
+use super::{legales};
+
+// bot que elige por "information set Monte Carlo tree search": en cada
+// iteracion reparte al azar las cartas que no ve (determinizacion) y
+// recorre un unico arbol compartido por todas las determinizaciones.
+// Busca hasta el final de la ronda; a mas iteraciones, mas fuerte.
+pub struct Ismcts {
+  pub iteraciones: usize,
+  // constante de exploracion del UCB
+  pub exploracion: f64,
+  pub allow_mazo: bool,
+  rng: StdRng,
+}
+
+// intentos para sortearle flor al que ya la canto
+const MAX_INTENTOS: usize = 1000;
+
+struct Nodo {
+  // la jugada que lleva a este nodo y el equipo que la hizo
+  jugada: Option<Jugada>,
+  equipo: Equipo,
+  hijos: Vec<usize>,
+  visitas: f64,
+  total: f64,
+  // veces que el nodo estuvo disponible al elegir desde su padre
+  disponible: f64,
+}
+
+impl Ismcts {
+  pub fn new(iteraciones: usize, seed: u64) -> Ismcts {
+    Ismcts {
+      iteraciones,
+      exploracion: 0.7,
+      allow_mazo: true,
+      rng: StdRng::seed_from_u64(seed),
+    }
+  }
+
+  // elige una jugada para `jid` mirando solo lo que `jid` puede ver de `p`
+  pub fn elegir(&mut self, p:&Partida, jid:&str) -> Result<Jugada, Error> {
+    let vista = p.perspectiva(jid)?;
+    let mix = vista.ronda.get_mix(jid)?;
+    let mut opciones = legales(&vista, mix, self.allow_mazo);
+    match opciones.len() {
+      0 => return Err(Error::JugadaIlegal(format!("{} no tiene jugadas validas", jid))),
+      1 => return Ok(opciones.remove(0)),
+      _ => (),
+    }
+
+    let equipo = vista.ronda.manojos[mix].jugador.equipo;
+    let mut arbol = vec![Nodo {
+      jugada: None,
+      equipo,
+      hijos: Vec::new(),
+      visitas: 0.0,
+      total: 0.0,
+      disponible: 0.0,
+    }];
+
+    for _ in 0..self.iteraciones {
+      let mut d = determinizar(&vista, equipo, &mut self.rng);
+      let antes = d.puntajes.clone();
+      let numero = d.ronda.numero;
+      let en_juego = |d: &Partida| d.ronda.numero == numero && !d.terminada();
+
+      // seleccion y expansion
+      let mut camino = vec![0];
+      let mut nodo = 0;
+      let mut actor = Some(mix);
+      while let Some(amix) = actor {
+        let opciones = legales(&d, amix, self.allow_mazo);
+        if opciones.is_empty() {
+          break
+        }
+        let sin_probar = opciones
+          .iter()
+          .filter(|j| !arbol[nodo].hijos.iter().any(|h| arbol[*h].jugada.as_ref() == Some(*j)))
+          .collect::<Vec<&Jugada>>();
+        let jugada = if let Some(j) = sin_probar.choose(&mut self.rng) {
+          let j = (*j).clone();
+          arbol.push(Nodo {
+            jugada: Some(j.clone()),
+            equipo: d.ronda.manojos[amix].jugador.equipo,
+            hijos: Vec::new(),
+            visitas: 0.0,
+            total: 0.0,
+            disponible: 1.0,
+          });
+          let hijo = arbol.len() - 1;
+          arbol[nodo].hijos.push(hijo);
+          nodo = hijo;
+          camino.push(nodo);
+          j.hacer(&mut d);
+          break
+        } else {
+          let hijos = arbol[nodo].hijos
+            .iter()
+            .copied()
+            .filter(|h| opciones.contains(arbol[*h].jugada.as_ref().unwrap()))
+            .collect::<Vec<usize>>();
+          for h in hijos.iter() {
+            arbol[*h].disponible += 1.0;
+          }
+          let c = self.exploracion;
+          let ucb = |n: &Nodo| n.total / n.visitas + c * (n.disponible.ln() / n.visitas).sqrt();
+          let mejor = *hijos
+            .iter()
+            .max_by(|a, b| ucb(&arbol[**a]).total_cmp(&ucb(&arbol[**b])))
+            .unwrap();
+          nodo = mejor;
+          camino.push(nodo);
+          arbol[nodo].jugada.clone().unwrap()
+        };
+        jugada.hacer(&mut d);
+        actor = if en_juego(&d) {elegir_actor(&d, self.allow_mazo).ok()} else {None};
+      }
+
+      // simulacion: al azar hasta que termine la ronda
+      while en_juego(&d) {
+        let Ok(amix) = elegir_actor(&d, self.allow_mazo) else { break };
+        let opciones = legales(&d, amix, self.allow_mazo);
+        let Some(j) = opciones.choose(&mut self.rng) else { break };
+        j.hacer(&mut d);
+      }
+
+      // retropropagacion: diferencia de puntos de la ronda, vista por
+      // el equipo que hizo la jugada de cada nodo
+      let ganados = |e: Equipo| d.puntajes[&e].saturating_sub(antes[&e]) as f64;
+      let dif = ((ganados(Equipo::Azul) - ganados(Equipo::Rojo)) / 10.0).clamp(-1.0, 1.0);
+      for n in camino {
+        let nodo = &mut arbol[n];
+        nodo.visitas += 1.0;
+        nodo.total += if nodo.equipo == Equipo::Azul {dif} else {-dif};
+      }
+    }
+
+    let mejor = arbol[0].hijos
+      .iter()
+      .max_by(|a, b| arbol[**a].visitas.total_cmp(&arbol[**b].visitas))
+      .and_then(|h| arbol[*h].jugada.clone());
+    Ok(mejor.unwrap_or_else(|| opciones.remove(0)))
+  }
+}
+
+// una copia de `vista` (lo que ve un jugador de `equipo`) con las cartas
+// que no ve repartidas al azar entre las que no vio
+pub fn determinizar(vista:&Partida, equipo:Equipo, rng:&mut StdRng) -> Partida {
+  let mut d = vista.clone();
+  let vistas = d.ronda.manojos
+    .iter()
+    .flat_map(|m| m.cartas.iter())
+    .filter(|c| c.es_valida())
+    .chain(std::iter::once(&d.ronda.muestra))
+    .map(|c| c.id())
+    .collect::<Vec<usize>>();
+  let mut mazo = (0..40)
+    .filter(|id| !vistas.contains(id))
+    .map(carta_from_id)
+    .collect::<Vec<Carta>>();
+  mazo.shuffle(rng);
+
+  // el que canto la flor tiene que tener flor
+  let cantaron_flor = std::iter::once(&d.ronda)
+    .chain(d.duelos.iter().map(|duelo| &duelo.partida.ronda))
+    .filter(|r| flor_cantada(r))
+    .filter_map(|r| r.envite.cantado_por.map(|ix| r.nombre(ix).to_string()))
+    .collect::<Vec<String>>();
+
+  // y el rival que tiro en primera sin que nadie cantara flor no la tiene
+  // (con flor no hubiera podido tirar sin cantarla)
+  let sin_flor = std::iter::once(&d.ronda)
+    .chain(d.duelos.iter().map(|duelo| &duelo.partida.ronda))
+    .filter(|r| r.reglas.con_flor && flor_sin_cantar(r))
+    .flat_map(|r| r.manos[0].cartas_tiradas
+      .iter()
+      .map(|t| &r.manojos[t.jugador].jugador)
+      .filter(|j| j.equipo != equipo)
+      .map(|j| j.id.clone()))
+    .collect::<Vec<String>>();
+
+  let mut sorteadas = HashMap::new();
+  let muestra = d.ronda.muestra;
+  for m in d.ronda.manojos.iter_mut() {
+    let ocultas = (0..3)
+      .filter(|ix| !m.cartas[*ix].es_valida())
+      .collect::<Vec<usize>>();
+    for ix in ocultas.iter() {
+      m.cartas[*ix] = mazo.pop().unwrap_or(m.cartas[*ix]);
+    }
+    let flor =
+      if cantaron_flor.contains(&m.jugador.id) {Some(true)}
+      else if sin_flor.contains(&m.jugador.id) {Some(false)}
+      else {None};
+    if let Some(flor) = flor {
+      for _ in 0..MAX_INTENTOS {
+        if m.tiene_flor(&muestra).0 == flor {
+          break
+        }
+        mazo.extend(ocultas.iter().map(|ix| m.cartas[*ix]));
+        mazo.shuffle(rng);
+        for ix in ocultas.iter() {
+          m.cartas[*ix] = mazo.pop().unwrap_or(m.cartas[*ix]);
+        }
+      }
+    }
+    sorteadas.insert(m.jugador.id.clone(), m.cartas);
+  }
+  recachear_flores(&mut d.ronda, equipo);
+  for duelo in d.duelos.iter_mut() {
+    for m in duelo.partida.ronda.manojos.iter_mut() {
+      m.cartas = sorteadas[&m.jugador.id];
+    }
+    recachear_flores(&mut duelo.partida.ronda, equipo);
+  }
+  d.ronda.rng = Some(StdRng::from_rng(rng).unwrap());
+  d
+}
+
+fn flor_cantada(r:&Ronda) -> bool {
+  [EstadoEnvite::Flor, EstadoEnvite::ContraFlor, EstadoEnvite::ContraFlorAlResto]
+    .contains(&r.envite.estado)
+}
+
+// nadie canto flor y todavia se puede: el que tenga flor no puede tirar
+// sin cantarla (ver `TirarCarta::validar_en_ronda`)
+fn flor_sin_cantar(r:&Ronda) -> bool {
+  r.envite.estado >= EstadoEnvite::NoCantadoAun &&
+    r.envite.estado <= EstadoEnvite::FaltaEnvido
+}
+
+// recalcula quienes tienen flor con las cartas sorteadas; un rival que
+// saco flor se supone sin cantar si todavia no jugo ninguna carta
+fn recachear_flores(r:&mut Ronda, equipo:Equipo) {
+  let mut sin_cantar = r.envite.sin_cantar.clone();
+  r.cachear_flores(false);
+  let primera = r.mano_en_juego == NumMano::Primera;
+  for (mix, m) in r.manojos.iter().enumerate() {
+    let es_rival = m.jugador.equipo != equipo;
+    let ya_la_canto = flor_cantada(r) && r.envite.cantado_por == Some(mix);
+    let no_jugo = m.get_cant_cartas_tiradas() == 0;
+    let ya_esta = sin_cantar.contains(&mix);
+    if es_rival && primera && no_jugo && !ya_la_canto && !ya_esta && r.tiene_flor(m).0 {
+      sin_cantar.push(mix);
+    }
+  }
+  r.envite.sin_cantar = sin_cantar;
+}
@@ -0,0 +1,97 @@
+mod ismcts;
+mod heuristico;
+
+pub use self::ismcts::{*};
+pub use self::heuristico::{*};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use crate::chi::{mascara, jugada_de_indice, CANT_ACCIONES};
+use crate::entorno::{elegir_actor};
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::jugada::{Jugada};
+use crate::partida::{Partida};
+
+// las jugadas validas del jugador de indice `mix`, en el orden de `chi`
+pub fn legales(p:&Partida, mix:usize, allow_mazo:bool) -> Vec<Jugada> {
+  let mask = mascara(p, mix, allow_mazo);
+  (0..CANT_ACCIONES)
+    .filter(|ix| mask & (1 << ix) != 0)
+    .filter_map(|ix| jugada_de_indice(p, mix, ix))
+    .collect()
+}
+
+// un jugador automatico: recibe lo que ve `jid` (ver `Partida::perspectiva`)
+// y sus jugadas validas (nunca vacias) y elige una de ellas
+pub trait Bot {
+  fn nombre(&self) -> String;
+  fn decidir(&mut self, vista:&Partida, jid:&str, legales:&[Jugada]) -> Jugada;
+}
+
+// elige al azar entre las jugadas validas
+pub struct Aleatorio {
+  rng: StdRng,
+}
+
+impl Aleatorio {
+  pub fn new(seed: u64) -> Aleatorio {
+    Aleatorio { rng: StdRng::seed_from_u64(seed) }
+  }
+}
+
+impl Bot for Aleatorio {
+  fn nombre(&self) -> String {
+    "aleatorio".to_string()
+  }
+  fn decidir(&mut self, _vista:&Partida, _jid:&str, legales:&[Jugada]) -> Jugada {
+    legales.choose(&mut self.rng).unwrap().clone()
+  }
+}
+
+impl Bot for Heuristico {
+  fn nombre(&self) -> String {
+    "heuristico".to_string()
+  }
+  fn decidir(&mut self, vista:&Partida, jid:&str, legales:&[Jugada]) -> Jugada {
+    match self.elegir(vista, jid) {
+      Ok(j) if legales.contains(&j) => j,
+      _ => legales[0].clone(),
+    }
+  }
+}
+
+impl Bot for Ismcts {
+  fn nombre(&self) -> String {
+    format!("ismcts:{}", self.iteraciones)
+  }
+  fn decidir(&mut self, vista:&Partida, jid:&str, legales:&[Jugada]) -> Jugada {
+    match self.elegir(vista, jid) {
+      Ok(j) if legales.contains(&j) => j,
+      _ => legales[0].clone(),
+    }
+  }
+}
+
+// juega `p` hasta el final; `bots[0]` juega por el equipo azul y
+// `bots[1]` por el rojo. Solo se van al mazo si no tienen otra.
+pub fn jugar_partida(p:&mut Partida, bots:&mut [Box<dyn Bot>; 2]) -> Result<(), Error> {
+  while !p.terminada() {
+    let mix = elegir_actor(p, false).or_else(|_| elegir_actor(p, true))?;
+    let mut opciones = legales(p, mix, false);
+    if opciones.is_empty() {
+      opciones = legales(p, mix, true);
+    }
+    let m = &p.ronda.manojos[mix];
+    let jid = m.jugador.id.clone();
+    let bot = &mut bots[if m.jugador.equipo == Equipo::Azul {0} else {1}];
+    let vista = p.perspectiva(&jid)?;
+    let jugada = bot.decidir(&vista, &jid, &opciones);
+    if !opciones.contains(&jugada) {
+      return Err(Error::JugadaIlegal(format!("{} eligio `{}`", bot.nombre(), p.comando(&jugada))))
+    }
+    p.jugar(&jugada);
+  }
+  Ok(())
+}
@@ -1,13 +1,17 @@
+use std::cmp::Ordering;
 use std::fmt;
+use rand::Rng;
 use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
+use crate::error::{Error};
+use crate::tablas;
 
 // usize ~ (0..=255)
-pub const PRIMES: &'static [usize] = &[
+pub const PRIMES: &[usize] = &[
   2, 3, 5, 7, 11, 13, 17, 19, 23, 29, // Basto
   31, 37, 41, 43, 47, 53, 59, 61, 67, 71, // Copa
   73, 79, 83, 89, 97, 101, 103, 107, 109, 113, // Espada
-  127, 131, 137, 139, 149, 151, 157, 163, 167, 17 // Oro
+  127, 131, 137, 139, 149, 151, 157, 163, 167, 173 // Oro
 ];
 
 
@@ -30,13 +34,13 @@ impl Palo {
     }
   }
 
-  pub fn parse(p: &str) -> Result<Palo, &str> {
+  pub fn parse(p: &str) -> Result<Palo, Error> {
     match p.to_lowercase().as_str() {
       "basto"  => Ok(Palo::Basto),
       "copa"   => Ok(Palo::Copa),
       "espada" => Ok(Palo::Espada),
       "oro"    => Ok(Palo::Oro),
-      _ => Err("Palo invalido"),
+      _ => Err(Error::CartaInvalida(format!("palo `{}`", p))),
     }
   }
 }
@@ -61,19 +65,25 @@ pub struct Carta {
 
 impl Carta {
   // constructor
-  pub fn new(valor: usize, palo: &str) -> Result<Carta, &str> {
-    if (8..=9).contains(&valor) || !(1 <= valor && valor <= 12) {
-      return Err("Valor invalido")
+  pub fn new(valor: usize, palo: &str) -> Result<Carta, Error> {
+    if (8..=9).contains(&valor) || !(1..=12).contains(&valor) {
+      return Err(Error::CartaInvalida(format!("valor {}", valor)))
     }
 
     Ok(
       Carta{
-        valor:valor,
+        valor,
         palo:Palo::parse(palo)?
       }
     )
   }
 
+  // false si el valor no existe en la baraja (ej. una carta deserializada)
+  pub fn es_valida(&self) -> bool {
+    // el palo siempre es valido; no hace falta pasar por `Carta::new`
+    matches!(self.valor, 1..=7 | 10..=12)
+  }
+
   pub fn id(&self) -> usize {
     let mut id = 10 * self.palo.to_int();
     id += self.valor - 1;
@@ -81,8 +91,9 @@ impl Carta {
     id 
   }
 
+  // un primo distinto por carta (ver `Manojo::clave`)
   pub fn puid(&self) -> usize {
-    return PRIMES[self.id() as usize]
+    PRIMES[self.id()]
   }
 
   pub fn es_numericamente_pieza(&self) -> bool {
@@ -90,6 +101,14 @@ impl Carta {
   }
 
   pub fn es_pieza(&self, muestra: &Carta) -> bool {
+    match (tablas::id_de(self), tablas::id_de(muestra)) {
+      (Some(cid), Some(mid)) => tablas::es_pieza(cid, mid),
+      _ => self.es_pieza_sin_tabla(muestra),
+    }
+  }
+
+  // igual que `es_pieza` pero sin usar las tablas (con las que se arman)
+  pub fn es_pieza_sin_tabla(&self, muestra: &Carta) -> bool {
     // caso 1
     let es_de_la_muestra = self.palo == muestra.palo;
     let es_pieza_caso_1 = self.es_numericamente_pieza() && es_de_la_muestra;
@@ -98,7 +117,7 @@ impl Carta {
     let es_pieza_caso_2 = es_doce && es_de_la_muestra &&
       muestra.es_numericamente_pieza();
 
-    return es_pieza_caso_1 || es_pieza_caso_2;
+    es_pieza_caso_1 || es_pieza_caso_2
   }
 
   pub fn es_mata(&self) -> bool {
@@ -114,7 +133,14 @@ impl Carta {
   }
 
   pub fn calc_puntaje(&self, muestra: &Carta) -> usize {
-    if self.es_pieza(muestra) {
+    match (tablas::id_de(self), tablas::id_de(muestra)) {
+      (Some(cid), Some(mid)) => tablas::puntaje(cid, mid),
+      _ => self.calc_puntaje_sin_tabla(muestra),
+    }
+  }
+
+  pub fn calc_puntaje_sin_tabla(&self, muestra: &Carta) -> usize {
+    if self.es_pieza_sin_tabla(muestra) {
       match self.valor {
         2       => 30,
         4       => 29,
@@ -122,26 +148,30 @@ impl Carta {
         10 | 11 => 27,
         12 => {
           let vale_como = Carta{valor:muestra.valor, palo:self.palo};
-          return vale_como.calc_puntaje(&muestra);
+          vale_como.calc_puntaje_sin_tabla(muestra)
         }
         _ => unreachable!()
       }
-    } else if self.es_mata() {
-      return self.valor
-    
-    } else if self.valor <= 3 {
-      return self.valor
+    } else if self.es_mata() || self.valor <= 3 {
+      self.valor
     
     } else if 10 <= self.valor && self.valor <= 12 {
-      return 0
+      0
     
     } else {
-      return self.valor
+      self.valor
     }
   }
 
   pub fn calc_poder(&self, muestra: &Carta) -> usize {
-    if self.es_pieza(muestra) {
+    match (tablas::id_de(self), tablas::id_de(muestra)) {
+      (Some(cid), Some(mid)) => tablas::poder(cid, mid),
+      _ => self.calc_poder_sin_tabla(muestra),
+    }
+  }
+
+  pub fn calc_poder_sin_tabla(&self, muestra: &Carta) -> usize {
+    if self.es_pieza_sin_tabla(muestra) {
       match self.valor {
         2  => 34,
         4  => 33,
@@ -150,7 +180,7 @@ impl Carta {
         10 => 30,
         12 => {
           let vale_como = Carta{valor:muestra.valor, palo:self.palo};
-          return vale_como.calc_puntaje(&muestra);
+          vale_como.calc_poder_sin_tabla(muestra)
         },
         _ => unreachable!()
       }
@@ -188,7 +218,21 @@ impl fmt::Display for Carta {
   }
 }
 
-fn carta_from_id(id: usize) -> Carta {
+// compara por poder a `a` y `b` con la muestra `muestra`; `Equal` si son
+// del mismo valor (ej. dos treses)
+pub fn comparar(a: &Carta, b: &Carta, muestra: &Carta) -> Ordering {
+  a.calc_poder(muestra).cmp(&b.calc_poder(muestra))
+}
+
+// las 40 cartas de la mas fuerte a la mas debil con la muestra `muestra`;
+// las que empatan quedan juntas, ordenadas por id
+pub fn ranking(muestra: &Carta) -> [Carta; 40] {
+  let mut cartas: [Carta; 40] = std::array::from_fn(carta_from_id);
+  cartas.sort_by(|a, b| comparar(b, a, muestra).then(a.id().cmp(&b.id())));
+  cartas
+}
+
+pub(crate) fn carta_from_id(id: usize) -> Carta {
   // valor
   let ultimo_digito = id % 10;
   let valor = 
@@ -196,20 +240,25 @@ fn carta_from_id(id: usize) -> Carta {
     else {10 + ultimo_digito - 7};
   let palo = 
     if id <= 9 {Palo::Basto}
-    else if 10 <= id && id <= 19 {Palo::Copa}
-    else if 20 <= id && id <= 29 {Palo::Espada}
+    else if (10..=19).contains(&id) {Palo::Copa}
+    else if (20..=29).contains(&id) {Palo::Espada}
     else {Palo::Oro};
   
   Carta{ valor, palo }
 }
 
 pub fn get_cartas_random(n: usize) -> Vec<Carta> {
+  get_cartas_random_rng(n, &mut rand::thread_rng())
+}
+
+// igual que `get_cartas_random` pero usando el generador `rng` dado;
+// con un generador con semilla fija el reparto es reproducible
+pub fn get_cartas_random_rng<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<Carta> {
   let max_carta_id = 40;
   let mut indices: Vec<usize> = (0..max_carta_id).collect();
-  let mut rng = rand::thread_rng();
-  indices.shuffle(&mut rng);
-  indices[0..n as usize]
+  indices.shuffle(rng);
+  indices[0..n]
     .iter()
     .map(|ix| carta_from_id(*ix))
     .collect::<Vec<Carta>>()
-}
+}
\ No newline at end of file
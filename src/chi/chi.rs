@@ -2,77 +2,85 @@ use rand::Rng;
 
 // use std::fmt;
 // use serde::{Deserialize, Serialize};
-use crate::{Partida, IJugada, Manojo};
+use crate::{Partida, IJugada};
 // use crate::{Packet};
 use crate::{enco};
 use crate::jugada::{*};
 use rand::seq::SliceRandom;
 
-pub fn is_done(pkts: &Vec<enco::Packet>) -> bool {
+pub fn is_done(pkts: &[enco::Packet]) -> bool {
   pkts
     .iter()
-    .any(|pkt| match pkt.message.0 {
-        crate::Content::NuevaPartida |
-        crate::Content::NuevaRonda => true,
-        crate::Content::RondaGanada{ autor: _, razon: _ } => true,
-        _ => false
-    })
+    .any(|pkt| matches!(
+      pkt.message.0,
+      crate::Content::NuevaPartida |
+      crate::Content::NuevaRonda |
+      crate::Content::RondaGanada{ .. }
+    ))
 }
 
-pub fn random_action_chi(chi: &Vec<Box<dyn IJugada>>) -> usize {
+pub fn random_action_chi(chi: &[Box<dyn IJugada>]) -> usize {
   rand::thread_rng().gen_range(0..chi.len())
 }
 
 // en el primer parametro de salida retorna un indice de manojo random
 // en el segundo retorna una jugada random de este manojo
-pub fn random_action_chis(chis: &Vec<Vec<Box<dyn IJugada>>>) -> (usize,usize) {
+pub fn random_action_chis(chis: &[Vec<Box<dyn IJugada>>]) -> (usize,usize) {
+  random_action_chis_rng(chis, &mut rand::thread_rng())
+}
+
+// igual que `random_action_chis` pero usando el generador `rng` dado
+pub fn random_action_chis_rng<R: Rng + ?Sized>(
+  chis: &[Vec<Box<dyn IJugada>>],
+  rng: &mut R,
+) -> (usize,usize) {
   let habilitados: Vec<usize>  =
     chis
       .iter()
       .enumerate()
-      .filter(|(_ix,chi)| chi.len() > 0)
+      .filter(|(_ix,chi)| !chi.is_empty())
       .map(|(ix, _chi)| ix)
       .collect();
     
-  let rmix = *habilitados.choose(&mut rand::thread_rng()).unwrap();
-  let raix = rand::thread_rng().gen_range(0..chis[rmix].len());
+  let rmix = *habilitados.choose(rng).unwrap();
+  let raix = rng.gen_range(0..chis[rmix].len());
 
   (rmix, raix)
 }
 
-// Retorna todas las acciones posibles para un jugador `m` dado
-pub fn chi(p:&Partida, m:&Manojo, allow_mazo:bool) -> Vec<Box<dyn IJugada>> {
+// Retorna todas las acciones posibles para el jugador de indice `mix`
+pub fn chi(p:&Partida, mix:usize, allow_mazo:bool) -> Vec<Box<dyn IJugada>> {
+  let m = &p.ronda.manojos[mix];
   let mut res: Vec<Box<dyn IJugada>> = vec![
     // cartas
-    Box::new(TirarCarta{jid: m.jugador.id.clone(), carta: m.cartas[0].clone()}),
-    Box::new(TirarCarta{jid: m.jugador.id.clone(), carta: m.cartas[1].clone()}),
-    Box::new(TirarCarta{jid: m.jugador.id.clone(), carta: m.cartas[2].clone()}),
+    Box::new(TirarCarta{jid: mix, carta: m.cartas[0]}),
+    Box::new(TirarCarta{jid: mix, carta: m.cartas[1]}),
+    Box::new(TirarCarta{jid: mix, carta: m.cartas[2]}),
     // toques
-    Box::new(TocarEnvido{jid: m.jugador.id.clone()}),
-    Box::new(TocarRealEnvido{jid: m.jugador.id.clone()}),
-    Box::new(TocarFaltaEnvido{jid: m.jugador.id.clone()}),
+    Box::new(TocarEnvido{jid: mix}),
+    Box::new(TocarRealEnvido{jid: mix}),
+    Box::new(TocarFaltaEnvido{jid: mix}),
     // cantos
-    Box::new(CantarFlor{jid: m.jugador.id.clone()}),
-    Box::new(CantarContraFlor{jid: m.jugador.id.clone()}),
-    Box::new(CantarContraFlorAlResto{jid: m.jugador.id.clone()}),
+    Box::new(CantarFlor{jid: mix}),
+    Box::new(CantarContraFlor{jid: mix}),
+    Box::new(CantarContraFlorAlResto{jid: mix}),
     // gritos
-    Box::new(GritarTruco{jid: m.jugador.id.clone()}),
-    Box::new(GritarReTruco{jid: m.jugador.id.clone()}),
-    Box::new(GritarVale4{jid: m.jugador.id.clone()}),
+    Box::new(GritarTruco{jid: mix}),
+    Box::new(GritarReTruco{jid: mix}),
+    Box::new(GritarVale4{jid: mix}),
     // respuestas
-    Box::new(ResponderQuiero{jid: m.jugador.id.clone()}),
-    Box::new(ResponderNoQuiero{jid: m.jugador.id.clone()}),
+    Box::new(ResponderQuiero{jid: mix}),
+    Box::new(ResponderNoQuiero{jid: mix}),
     // mazo
-    Box::new(IrseAlMazo{jid: m.jugador.id.clone()})
+    Box::new(IrseAlMazo{jid: mix})
   ];
 
-  res = res.into_iter().filter(|j| j.ok(p).1).collect();
+  res.retain(|j| j.ok(p).1);
 
-  if !allow_mazo && res.len() > 0 {
-    if res.last().unwrap().id() == IJugadaId::JIdMazo {
+  if !allow_mazo && !res.is_empty()
+    && res.last().unwrap().id() == IJugadaId::JIdMazo {
       res.truncate(res.len() - 1);
     }
-  }
  
   res
 }
@@ -80,12 +88,22 @@ pub fn chi(p:&Partida, m:&Manojo, allow_mazo:bool) -> Vec<Box<dyn IJugada>> {
 pub fn chis(p:&Partida, allow_mazo:bool) -> Vec<Vec<Box<dyn IJugada>>> {
   p.ronda.manojos
     .iter()
-    .map(|m| chi(p, m, allow_mazo))
+    .enumerate()
+    .map(|(mix, _)| chi(p, mix, allow_mazo))
     .collect()
 }
 
 pub fn random_action(p:&Partida, allow_mazo:bool) -> Box<dyn IJugada> {
+  random_action_rng(p, allow_mazo, &mut rand::thread_rng())
+}
+
+// igual que `random_action` pero usando el generador `rng` dado
+pub fn random_action_rng<R: Rng + ?Sized>(
+  p:&Partida,
+  allow_mazo:bool,
+  rng: &mut R,
+) -> Box<dyn IJugada> {
   let mut chiss = chis(p, allow_mazo);
-  let (rmix, raix) = random_action_chis(&chiss);
+  let (rmix, raix) = random_action_chis_rng(&chiss, rng);
   chiss.remove(rmix).remove(raix)
 }
\ No newline at end of file
@@ -0,0 +1,66 @@
+use crate::{Partida, IJugada};
+use crate::jugada::{*};
+
+// espacio de acciones fijo para agentes: los 3 primeros indices son las
+// cartas del manojo (por posicion) y los 12 siguientes las demas jugadas
+// en el orden de `IJugadaId` (indice = 2 + id)
+pub const CANT_ACCIONES: usize = 15;
+
+// la jugada que corresponde al indice `ix` para el jugador `mix`
+pub fn jugada_de_indice(p:&Partida, mix:usize, ix:usize) -> Option<Jugada> {
+  let m = &p.ronda.manojos[mix];
+  let jid = mix;
+  let jugada = match ix {
+    0..=2 => Jugada::TirarCarta(TirarCarta{jid, carta: m.cartas[ix]}),
+    3 => Jugada::TocarEnvido(TocarEnvido{jid}),
+    4 => Jugada::TocarRealEnvido(TocarRealEnvido{jid}),
+    5 => Jugada::TocarFaltaEnvido(TocarFaltaEnvido{jid}),
+    6 => Jugada::CantarFlor(CantarFlor{jid}),
+    7 => Jugada::CantarContraFlor(CantarContraFlor{jid}),
+    8 => Jugada::CantarContraFlorAlResto(CantarContraFlorAlResto{jid}),
+    9 => Jugada::GritarTruco(GritarTruco{jid}),
+    10 => Jugada::GritarReTruco(GritarReTruco{jid}),
+    11 => Jugada::GritarVale4(GritarVale4{jid}),
+    12 => Jugada::ResponderQuiero(ResponderQuiero{jid}),
+    13 => Jugada::ResponderNoQuiero(ResponderNoQuiero{jid}),
+    14 => Jugada::IrseAlMazo(IrseAlMazo{jid}),
+    _ => return None,
+  };
+  Some(jugada)
+}
+
+// inversa de `jugada_de_indice`
+pub fn indice_de_jugada(p:&Partida, mix:usize, j:&Jugada) -> Option<usize> {
+  if j.jid() != mix {
+    return None
+  }
+  let m = &p.ronda.manojos[mix];
+  match j {
+    Jugada::TirarCarta(t) => m.cartas.iter().position(|c| *c == t.carta),
+    _ => Some(2 + j.id() as usize),
+  }
+}
+
+// bit `i` prendido sii la jugada de indice `i` es valida para `mix`;
+// coincide con lo que retorna `chi` (sin alocar cada jugada)
+pub fn mascara(p:&Partida, mix:usize, allow_mazo:bool) -> u16 {
+  let mut res = 0;
+  for ix in 0..CANT_ACCIONES {
+    if !allow_mazo && ix == 2 + IJugadaId::JIdMazo as usize {
+      continue
+    }
+    let ok = jugada_de_indice(p, mix, ix).is_some_and(|j| j.ok(p).1);
+    if ok {
+      res |= 1 << ix;
+    }
+  }
+  res
+}
+
+pub fn mascaras(p:&Partida, allow_mazo:bool) -> Vec<u16> {
+  p.ronda.manojos
+    .iter()
+    .enumerate()
+    .map(|(mix, _)| mascara(p, mix, allow_mazo))
+    .collect()
+}
@@ -1,3 +1,6 @@
+#[allow(clippy::module_inception)]
 mod chi;
+mod mascara;
 
-pub use self::chi::{*};
\ No newline at end of file
+pub use self::chi::{*};
+pub use self::mascara::{*};
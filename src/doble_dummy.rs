@@ -0,0 +1,166 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::carta::{Carta};
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::jugada::{IJugada, TirarCarta};
+use crate::mano::{NumMano, Resultado};
+use crate::partida::{Partida};
+
+// un punto de la linea principal en el que `jugador` tiene que tirar: el
+// resultado de la ronda para cada carta que puede tirar y la optima
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct Decision {
+  // indice del jugador en `Ronda.manojos`
+  pub jugador: usize,
+  pub mano: NumMano,
+  pub evaluaciones: Vec<(Carta, Equipo)>,
+  pub optima: Carta,
+}
+
+// el analisis "doble dummy" del resto de la ronda: con todas las cartas a
+// la vista y sin mas cantos, quien gana si los 2 equipos tiran perfecto
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct DobleDummy {
+  pub ganador: Equipo,
+  pub decisiones: Vec<Decision>,
+}
+
+impl Partida {
+  // resuelve lo que queda de la ronda tirando solo cartas; falla si hay
+  // algo pendiente que no sea tirar (un truco sin responder, una flor sin
+  // cantar, el envite en juego) o en una ronda de pica-pica
+  pub fn doble_dummy(&self) -> Result<DobleDummy, Error> {
+    let mut p = self.para_resolver()?;
+    let numero = p.ronda.numero;
+    let mut decisiones = Vec::new();
+    let mut memo = Memo::new();
+    let ganador = loop {
+      let evaluaciones = evaluar(&p, &mut memo)?;
+      let jugador = p.ronda.turno;
+      let equipo = p.ronda.manojos[jugador].jugador.equipo;
+      // de las que dan el mejor resultado, la de menor poder
+      let gana = evaluaciones.iter().any(|(_, e)| *e == equipo);
+      let optima = evaluaciones
+        .iter()
+        .filter(|(_, e)| !gana || *e == equipo)
+        .min_by_key(|(c, _)| c.calc_poder(&p.ronda.muestra))
+        .map(|(c, _)| *c)
+        .unwrap();
+      decisiones.push(Decision {
+        jugador,
+        mano: p.ronda.mano_en_juego,
+        evaluaciones,
+        optima,
+      });
+      let antes = p.puntajes.clone();
+      TirarCarta{jid: jugador, carta: optima}.hacer(&mut p);
+      if p.ronda.numero != numero || p.terminada() {
+        break ganador_por_puntos(&p, &antes)?;
+      }
+    };
+    Ok(DobleDummy { ganador, decisiones })
+  }
+
+  // el resultado de la ronda (con juego perfecto de ahi en mas) para cada
+  // carta que puede tirar el jugador del turno
+  pub fn evaluar_tiradas(&self) -> Result<Vec<(Carta, Equipo)>, Error> {
+    evaluar(&self.para_resolver()?, &mut Memo::new())
+  }
+
+  fn para_resolver(&self) -> Result<Partida, Error> {
+    if self.terminada() {
+      return Err(Error::PartidaTerminada);
+    }
+    if self.en_pica_pica() {
+      return Err(Error::EstadoInvalido(
+        "no se puede resolver una ronda de pica-pica".to_string()));
+    }
+    Ok(self.para_buscar())
+  }
+}
+
+// las cartas que puede tirar el del turno (al menos una)
+fn tiradas(p: &Partida) -> Result<Vec<TirarCarta>, Error> {
+  let jid = p.ronda.turno;
+  let m = &p.ronda.manojos[jid];
+  let res = (0..3)
+    .filter(|ix| !m.tiradas[*ix])
+    .map(|ix| TirarCarta{jid, carta: m.cartas[ix]})
+    .filter(|t| t.ok(p).1)
+    .collect::<Vec<TirarCarta>>();
+  if res.is_empty() {
+    return Err(Error::EstadoInvalido(
+      "el jugador del turno no puede tirar ninguna carta".to_string()));
+  }
+  Ok(res)
+}
+
+fn evaluar(p: &Partida, memo: &mut Memo) -> Result<Vec<(Carta, Equipo)>, Error> {
+  tiradas(p)?
+    .into_iter()
+    .map(|t| Ok((t.carta, resultado(p, &t, memo)?)))
+    .collect()
+}
+
+// lo que decide el resto de la ronda cuando solo se tiran cartas: que ya
+// tiro cada uno, como salieron las manos jugadas y lo que hay en la mesa.
+// Varios ordenes de tiradas llevan a la misma clave
+type Clave = (Vec<[bool; 3]>, Vec<(Resultado, Option<usize>)>, Vec<(usize, Carta)>);
+type Memo = HashMap<Clave, Equipo>;
+
+fn clave(p: &Partida) -> Clave {
+  let r = &p.ronda;
+  let actual = r.mano_en_juego as usize;
+  (
+    r.manojos.iter().map(|m| m.tiradas).collect(),
+    r.manos[..actual].iter().map(|m| (m.resultado, m.ganador)).collect(),
+    r.manos[actual].cartas_tiradas.iter().map(|t| (t.jugador, t.carta)).collect(),
+  )
+}
+
+// quien gana la ronda si se tira `t` y de ahi en mas se juega perfecto
+fn resultado(p: &Partida, t: &TirarCarta, memo: &mut Memo) -> Result<Equipo, Error> {
+  let mut q = p.clone();
+  let numero = q.ronda.numero;
+  let antes = q.puntajes.clone();
+  t.hacer(&mut q);
+  if q.ronda.numero != numero || q.terminada() {
+    return ganador_por_puntos(&q, &antes);
+  }
+  ganador(&q, memo)
+}
+
+// minimax con resultado binario: apenas una carta le da la ronda al equipo
+// del turno no hace falta ver las demas
+fn ganador(p: &Partida, memo: &mut Memo) -> Result<Equipo, Error> {
+  let clave = clave(p);
+  if let Some(e) = memo.get(&clave) {
+    return Ok(*e);
+  }
+  let equipo = p.ronda.get_el_turno().jugador.equipo;
+  let mut res = equipo.equipo_contrario();
+  for t in tiradas(p)?.iter() {
+    if resultado(p, t, memo)? == equipo {
+      res = equipo;
+      break;
+    }
+  }
+  memo.insert(clave, res);
+  Ok(res)
+}
+
+// al terminar la ronda los puntos del truco van al equipo que la gano
+fn ganador_por_puntos(
+  p: &Partida,
+  antes: &HashMap<Equipo, usize>,
+) -> Result<Equipo, Error> {
+  let delta = |e: Equipo| p.puntajes[&e].saturating_sub(antes[&e]);
+  match delta(Equipo::Azul).cmp(&delta(Equipo::Rojo)) {
+    Ordering::Greater => Ok(Equipo::Azul),
+    Ordering::Less => Ok(Equipo::Rojo),
+    Ordering::Equal => Err(Error::EstadoInvalido(
+      "la ronda termino sin ganador".to_string())),
+  }
+}
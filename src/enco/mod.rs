@@ -1,3 +1,5 @@
 mod packet;
+mod tensor;
 
-pub use self::packet::{*};
\ No newline at end of file
+pub use self::packet::{*};
+pub use self::tensor::{*};
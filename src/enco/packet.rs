@@ -1,14 +1,14 @@
 use std::fmt;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub struct Packet {
   pub destination: Vec<String>,
   pub message: Message,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Razon {
   EnvidoGanado,
@@ -46,7 +46,7 @@ impl fmt::Display for Razon {
   }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum Content {
   // sin nada
@@ -93,7 +93,7 @@ pub enum Content {
 impl Content {
   fn cod(&self) -> String {
     match self {
-      Content::LaManoResultaParda{} => String::from("LaManoResultaParda"),
+      Content::LaManoResultaParda => String::from("LaManoResultaParda"),
       Content::Error{msg: _} => String::from("Error"),
       Content::ByeBye{msg: _} => String::from("ByeBye"),
       Content::DiceSonBuenas{autor: _} => String::from("DiceSonBuenas"),
@@ -119,8 +119,8 @@ impl Content {
       Content::DiceSonMejores{autor: _, valor: _} => String::from("DiceSonMejores"),
       Content::ManoGanada{autor: _, valor: _} => String::from("ManoGanada"),
       Content::RondaGanada{autor: _, razon: _} => String::from("RondaGanada"),
-      Content::NuevaPartida{} => String::from("NuevaPartida"),
-      Content::NuevaRonda{} => String::from("NuevaRonda"),
+      Content::NuevaPartida => String::from("NuevaPartida"),
+      Content::NuevaRonda => String::from("NuevaRonda"),
       Content::TirarCarta{autor: _, palo: _, valor: _} => String::from("TirarCarta"),
       Content::SumaPts{autor:_, razon:_, pts:_} => String::from("SumaPts"),
     }
@@ -128,8 +128,10 @@ impl Content {
 }
 
 use serde::ser::{Serializer, SerializeStruct};
+use serde::de::{self, Deserializer, DeserializeOwned};
+use serde_json::Value;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Message(pub Content);
 
 impl Serialize for Message {
@@ -153,4 +155,63 @@ impl Serialize for Message {
     }
     state.end()
   }
-}
\ No newline at end of file
+}
+
+// lee el campo `k` de `cont`
+fn campo<T: DeserializeOwned, E: de::Error>(
+  cont: &Value,
+  k: &'static str
+) -> Result<T, E> {
+  let v = cont.get(k).ok_or_else(|| E::missing_field(k))?;
+  T::deserialize(v).map_err(E::custom)
+}
+
+impl<'de> Deserialize<'de> for Message {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+      D: Deserializer<'de>,
+  {
+    #[derive(Deserialize)]
+    struct Crudo {
+      cod: String,
+      #[serde(default)]
+      cont: Value,
+    }
+
+    let Crudo{cod, cont} = Crudo::deserialize(deserializer)?;
+    let content = match cod.as_str() {
+      "LaManoResultaParda" => Content::LaManoResultaParda,
+      "Error" => Content::Error{msg: campo(&cont, "msg")?},
+      "ByeBye" => Content::ByeBye{msg: campo(&cont, "msg")?},
+      "DiceSonBuenas" => Content::DiceSonBuenas{autor: campo(&cont, "autor")?},
+      "CantarFlor" => Content::CantarFlor{autor: campo(&cont, "autor")?},
+      "CantarContraFlor" => Content::CantarContraFlor{autor: campo(&cont, "autor")?},
+      "CantarContraFlorAlResto" => Content::CantarContraFlorAlResto{autor: campo(&cont, "autor")?},
+      "TocarEnvido" => Content::TocarEnvido{autor: campo(&cont, "autor")?},
+      "TocarRealEnvido" => Content::TocarRealEnvido{autor: campo(&cont, "autor")?},
+      "TocarFaltaEnvido" => Content::TocarFaltaEnvido{autor: campo(&cont, "autor")?},
+      "GritarTruco" => Content::GritarTruco{autor: campo(&cont, "autor")?},
+      "GritarReTruco" => Content::GritarReTruco{autor: campo(&cont, "autor")?},
+      "GritarVale4" => Content::GritarVale4{autor: campo(&cont, "autor")?},
+      "NoQuiero" => Content::NoQuiero{autor: campo(&cont, "autor")?},
+      "ConFlorMeAchico" => Content::ConFlorMeAchico{autor: campo(&cont, "autor")?},
+      "QuieroTruco" => Content::QuieroTruco{autor: campo(&cont, "autor")?},
+      "QuieroEnvite" => Content::QuieroEnvite{autor: campo(&cont, "autor")?},
+      "Mazo" => Content::Mazo{autor: campo(&cont, "autor")?},
+      "ElEnvidoEstaPrimero" => Content::ElEnvidoEstaPrimero{autor: campo(&cont, "autor")?},
+      "Abandono" => Content::Abandono{autor: campo(&cont, "autor")?},
+      "SigTurno" => Content::SigTurno{pos: campo(&cont, "pos")?},
+      "SigTurnoPosMano" => Content::SigTurnoPosMano{pos: campo(&cont, "pos")?},
+      "DiceTengo" => Content::DiceTengo{autor: campo(&cont, "autor")?, valor: campo(&cont, "valor")?},
+      "DiceSonMejores" => Content::DiceSonMejores{autor: campo(&cont, "autor")?, valor: campo(&cont, "valor")?},
+      "ManoGanada" => Content::ManoGanada{autor: campo(&cont, "autor")?, valor: campo(&cont, "valor")?},
+      "RondaGanada" => Content::RondaGanada{autor: campo(&cont, "autor")?, razon: campo(&cont, "razon")?},
+      "NuevaPartida" => Content::NuevaPartida,
+      "NuevaRonda" => Content::NuevaRonda,
+      "TirarCarta" => Content::TirarCarta{autor: campo(&cont, "autor")?, palo: campo(&cont, "palo")?, valor: campo(&cont, "valor")?},
+      "SumaPts" => Content::SumaPts{autor: campo(&cont, "autor")?, razon: campo(&cont, "razon")?, pts: campo(&cont, "pts")?},
+      otro => return Err(de::Error::custom(format!("cod desconocido `{}`", otro))),
+    };
+    Ok(Message(content))
+  }
+}
@@ -0,0 +1,109 @@
+use std::iter;
+use crate::error::{Error};
+use crate::observacion::{Observacion};
+use crate::partida::{Partida};
+
+// codificacion numerica de lo que un jugador ve de la partida, para agentes
+// de aprendizaje. El layout es estable: cualquier cambio es incompatible y
+// tiene que cambiar `TENSOR_VERSION`.
+//
+// Los asientos son relativos al jugador (0 = el, 1 = el siguiente, ...).
+// En una ronda de pica-pica se codifica su duelo.
+//
+//  offset  largo  contenido
+//  ------  -----  ---------
+//     0      40   sus cartas sin tirar, one-hot por `Carta::id`
+//    40      40   la muestra, one-hot por `Carta::id`
+//    80     240   las cartas tiradas por cada asiento (6 x 40)
+//   320       8   `EstadoEnvite`, one-hot
+//   328       7   `EstadoTruco`, one-hot
+//   335       2   puntaje propio y del rival, divididos por la puntuacion
+//   337       3   `mano_en_juego`, one-hot
+//   340       6   asiento del turno, one-hot
+//   346       6   asiento del mano, one-hot
+//   352       6   su asiento absoluto en la ronda, one-hot
+//   358       1   1 si tiene flor sin cantar
+pub const TENSOR_VERSION: u32 = 1;
+pub const TENSOR_LEN: usize = 359;
+
+const CANT_CARTAS: usize = 40;
+const MAX_ASIENTOS: usize = 6;
+
+const OFFSET_MANO: usize = 0;
+const OFFSET_MUESTRA: usize = OFFSET_MANO + CANT_CARTAS;
+const OFFSET_TIRADAS: usize = OFFSET_MUESTRA + CANT_CARTAS;
+const OFFSET_ENVITE: usize = OFFSET_TIRADAS + MAX_ASIENTOS * CANT_CARTAS;
+const OFFSET_TRUCO: usize = OFFSET_ENVITE + 8;
+const OFFSET_PUNTAJES: usize = OFFSET_TRUCO + 7;
+const OFFSET_MANO_EN_JUEGO: usize = OFFSET_PUNTAJES + 2;
+const OFFSET_TURNO: usize = OFFSET_MANO_EN_JUEGO + 3;
+const OFFSET_EL_MANO: usize = OFFSET_TURNO + MAX_ASIENTOS;
+const OFFSET_ASIENTO: usize = OFFSET_EL_MANO + MAX_ASIENTOS;
+const OFFSET_FLOR: usize = OFFSET_ASIENTO + MAX_ASIENTOS;
+const _: () = assert!(OFFSET_FLOR + 1 == TENSOR_LEN);
+
+pub fn codificar(p:&Partida, jid:&str) -> Result<Vec<f32>, Error> {
+  let o = p.observar(jid)?;
+  let o = match &o.duelo {
+    Some(d) => d.as_ref(),
+    None => &o,
+  };
+  codificar_observacion(o)
+}
+
+fn invalida(msg: &str) -> Error {
+  Error::EstadoInvalido(format!("observacion invalida: {}", msg))
+}
+
+// la observacion puede venir de afuera (ej. deserializada), asi que se
+// checkea todo lo que se usa como indice antes de codificarla
+pub fn codificar_observacion(o:&Observacion) -> Result<Vec<f32>, Error> {
+  let mut t = vec![0.0; TENSOR_LEN];
+  let n = o.manojos.len();
+  if ![2, 4, 6].contains(&n) {
+    return Err(Error::CantidadDeJugadoresInvalida);
+  }
+  if o.turno >= n || o.el_mano >= n {
+    return Err(invalida("turno o mano fuera de la ronda"));
+  }
+  let cartas = o.manojos.iter().flat_map(|m| m.cartas.iter().flatten());
+  if !iter::once(&o.muestra).chain(cartas).all(|c| c.es_valida()) {
+    return Err(invalida("carta fuera de la baraja"));
+  }
+  let yo = o.manojos
+    .iter()
+    .position(|m| m.jugador.id == o.jid)
+    .ok_or_else(|| Error::JugadorInexistente(o.jid.clone()))?;
+  let asiento = |ix:usize| (ix + n - yo) % n;
+
+  for (ix, m) in o.manojos.iter().enumerate() {
+    for (cix, c) in m.cartas.iter().enumerate() {
+      let Some(c) = c else { continue };
+      if m.tiradas[cix] {
+        t[OFFSET_TIRADAS + asiento(ix) * CANT_CARTAS + c.id()] = 1.0;
+      } else if ix == yo {
+        t[OFFSET_MANO + c.id()] = 1.0;
+      }
+    }
+  }
+  t[OFFSET_MUESTRA + o.muestra.id()] = 1.0;
+
+  t[OFFSET_ENVITE + o.envite.estado as usize] = 1.0;
+  t[OFFSET_TRUCO + o.truco.estado as usize] = 1.0;
+
+  let equipo = o.manojos[yo].jugador.equipo;
+  let rival = equipo.equipo_contrario();
+  let puntuacion = o.puntuacion.max(1) as f32;
+  let puntaje = |e| o.puntajes.get(&e).copied().unwrap_or(0) as f32 / puntuacion;
+  t[OFFSET_PUNTAJES] = puntaje(equipo);
+  t[OFFSET_PUNTAJES + 1] = puntaje(rival);
+
+  t[OFFSET_MANO_EN_JUEGO + o.mano_en_juego as usize] = 1.0;
+  t[OFFSET_TURNO + asiento(o.turno)] = 1.0;
+  t[OFFSET_EL_MANO + asiento(o.el_mano)] = 1.0;
+  t[OFFSET_ASIENTO + yo] = 1.0;
+  if o.flor_pendiente {
+    t[OFFSET_FLOR] = 1.0;
+  }
+  Ok(t)
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use crate::chi::{mascara, jugada_de_indice, CANT_ACCIONES};
+use crate::enco;
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::jugada::{IJugada, IJugadaId};
+use crate::partida::{Partida};
+use crate::reglas::{Reglas};
+
+// lo que devuelven `reset` y `step`
+#[derive(Debug, Clone)]
+pub struct Paso {
+  // indice en `Ronda.manojos` del jugador que tiene que actuar
+  pub actor: usize,
+  // ver `enco::codificar`; es la vista del `actor`
+  pub observacion: Vec<f32>,
+  // ver `chi::mascara`
+  pub mascara: u16,
+  // la recompensa es por jugada, no por ronda: los puntos que gano cada
+  // equipo con la ultima jugada (el envite se cobra apenas se resuelve y
+  // el truco al final de la ronda). Sumadas a lo largo de una ronda dan
+  // los puntos de esa ronda
+  pub recompensas: HashMap<Equipo, usize>,
+  // en la jugada que termina una ronda, los puntos que gano cada equipo en
+  // toda esa ronda; `None` en las demas
+  pub recompensas_ronda: Option<HashMap<Equipo, usize>>,
+  pub terminado: bool,
+}
+
+// entorno estilo gym para entrenar agentes: una partida en la que se van
+// eligiendo jugadas por su indice en el espacio de `chi::mascara`
+pub struct Entorno {
+  pub puntuacion: usize,
+  pub azules: Vec<String>,
+  pub rojos: Vec<String>,
+  pub reglas: Reglas,
+  pub allow_mazo: bool,
+  pub partida: Partida,
+  actor: usize,
+  // los puntajes al empezar la ronda en juego
+  puntajes_ronda: HashMap<Equipo, usize>,
+}
+
+const QUIERO: usize = 2 + IJugadaId::JIdQuiero as usize;
+const NO_QUIERO: usize = 2 + IJugadaId::JIdNoQuiero as usize;
+const FLOR: usize = 2 + IJugadaId::JIdFlor as usize;
+
+impl Entorno {
+  pub fn new(
+    puntuacion: usize,
+    azules: Vec<String>,
+    rojos: Vec<String>,
+    reglas: Reglas,
+    allow_mazo: bool,
+  ) -> Result<Entorno, Error> {
+    let partida = Partida::new_con_reglas(
+      puntuacion, azules.clone(), rojos.clone(), reglas, false)?;
+    let puntajes_ronda = partida.puntajes.clone();
+    Ok(Entorno {
+      puntuacion,
+      azules,
+      rojos,
+      reglas,
+      allow_mazo,
+      partida,
+      actor: 0,
+      puntajes_ronda,
+    })
+  }
+
+  pub fn reset(&mut self, seed: u64) -> Result<Paso, Error> {
+    self.partida = Partida::new_with_reglas_y_rng(
+      self.puntuacion,
+      self.azules.clone(),
+      self.rojos.clone(),
+      self.reglas,
+      false,
+      Some(rand::SeedableRng::seed_from_u64(seed)),
+    )?;
+    self.puntajes_ronda = self.partida.puntajes.clone();
+    let recompensas = [(Equipo::Azul, 0), (Equipo::Rojo, 0)].into();
+    self.paso(recompensas, None)
+  }
+
+  // juega la accion `ix` del actor actual
+  pub fn step(&mut self, ix: usize) -> Result<Paso, Error> {
+    let p = &self.partida;
+    if p.terminada() {
+      return Err(Error::PartidaTerminada);
+    }
+    let m = &p.ronda.manojos[self.actor];
+    if ix >= CANT_ACCIONES || mascara(p, self.actor, self.allow_mazo) & (1 << ix) == 0 {
+      return Err(Error::JugadaIlegal(
+        format!("la accion {} no esta habilitada para {}", ix, m.jugador.id)));
+    }
+    let jugada = jugada_de_indice(p, self.actor, ix).unwrap();
+    let antes = p.puntajes.clone();
+    let numero = p.ronda.numero;
+    // sin registrar: el historial y lo deshecho no le sirven al entorno
+    jugada.hacer(&mut self.partida);
+    let p = &self.partida;
+    let recompensas = diferencia(&p.puntajes, &antes);
+    let recompensas_ronda =
+      if p.ronda.numero != numero || p.terminada() {
+        let res = diferencia(&p.puntajes, &self.puntajes_ronda);
+        self.puntajes_ronda = p.puntajes.clone();
+        Some(res)
+      } else {
+        None
+      };
+    self.paso(recompensas, recompensas_ronda)
+  }
+
+  pub fn actor(&self) -> usize {
+    self.actor
+  }
+
+  fn paso(
+    &mut self,
+    recompensas: HashMap<Equipo, usize>,
+    recompensas_ronda: Option<HashMap<Equipo, usize>>,
+  ) -> Result<Paso, Error> {
+    let terminado = self.partida.terminada();
+    if !terminado {
+      self.actor = elegir_actor(&self.partida, self.allow_mazo)?;
+    }
+    let p = &self.partida;
+    let m = &p.ronda.manojos[self.actor];
+    Ok(Paso {
+      actor: self.actor,
+      observacion: enco::codificar(p, &m.jugador.id)?,
+      mascara: if terminado {0} else {mascara(p, self.actor, self.allow_mazo)},
+      recompensas,
+      recompensas_ronda,
+      terminado,
+    })
+  }
+}
+
+// los puntos que gano cada equipo de `antes` a `despues`
+fn diferencia(
+  despues: &HashMap<Equipo, usize>,
+  antes: &HashMap<Equipo, usize>,
+) -> HashMap<Equipo, usize> {
+  despues
+    .iter()
+    .map(|(e, pts)| (*e, pts.saturating_sub(antes.get(e).copied().unwrap_or(0))))
+    .collect()
+}
+
+// quien actua: primero el que tiene que responder un quiero/no-quiero,
+// despues el que tiene una flor por cantar, despues el del turno y si no
+// cualquiera que pueda hacer algo (recorriendo desde el turno). En una
+// ronda de pica-pica el criterio se aplica al primer duelo sin terminar.
+pub fn elegir_actor(p:&Partida, allow_mazo:bool) -> Result<usize, Error> {
+  let q = p.duelos
+    .iter()
+    .find(|d| !d.terminado)
+    .map(|d| &d.partida)
+    .unwrap_or(p);
+  let n = q.ronda.manojos.len();
+  let orden = (0..n)
+    .map(|i| (q.ronda.turno + i) % n)
+    .map(|mix| (mix, mascara(q, mix, allow_mazo)))
+    .collect::<Vec<(usize, u16)>>();
+
+  let tiene = |bits: &[usize]| orden
+    .iter()
+    .find(|(_, mask)| bits.iter().any(|b| mask & (1 << b) != 0))
+    .map(|(mix, _)| *mix);
+  let mix = tiene(&[QUIERO, NO_QUIERO])
+    .or_else(|| tiene(&[FLOR]))
+    .or_else(|| orden.iter().find(|(_, mask)| *mask != 0).map(|(mix, _)| *mix))
+    .ok_or_else(|| Error::EstadoInvalido("ningun jugador puede actuar".to_string()))?;
+
+  let jid = &q.ronda.manojos[mix].jugador.id;
+  p.ronda.mixs
+    .get(jid)
+    .copied()
+    .ok_or_else(|| Error::JugadorInexistente(jid.clone()))
+}
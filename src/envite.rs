@@ -46,28 +46,31 @@ pub struct Envite<'a> {
 
 */
 
+// los jugadores se identifican por su indice en `Ronda.manojos`
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Envite {
   pub estado: EstadoEnvite,
   pub puntaje: usize,
-  pub cantado_por: String,
-  #[serde(skip_deserializing, skip_serializing)]
-  pub jugadores_con_flor: Vec<String>,
-  pub sin_cantar: Vec<String>,
+  pub cantado_por: Option<usize>,
+  // se serializa porque no se puede deducir de los manojos: quien tiene
+  // flor y se fue al mazo ya no cuenta
+  #[serde(default)]
+  pub jugadores_con_flor: Vec<usize>,
+  pub sin_cantar: Vec<usize>,
 }
 
 // si se usa `jugadores_con_flor` con referencias
 // impl Envite<'_> {
 
 impl Envite {
-  pub fn new(con_flor: Vec<String>) -> Envite {
+  pub fn new(con_flor: Vec<usize>) -> Envite {
     let sin_cantar = con_flor.clone();
     Envite{
       estado: EstadoEnvite::NoCantadoAun,
       puntaje: 0,
-      cantado_por: String::from(""),
+      cantado_por: None,
       jugadores_con_flor: con_flor,
-      sin_cantar: sin_cantar,
+      sin_cantar,
     }
   }
 
@@ -75,18 +78,18 @@ impl Envite {
     // OJO: no le resetea `.jugadores_con_flor` y `sin_cantar` !!
     self.estado = EstadoEnvite::NoCantadoAun;
     self.puntaje = 0;
-    self.cantado_por = String::from("");
+    self.cantado_por = None;
   }
 
-  pub fn no_canto_flor_aun(&self, j:&String) -> bool {
-    self.sin_cantar.contains(j)
+  pub fn no_canto_flor_aun(&self, j:usize) -> bool {
+    self.sin_cantar.contains(&j)
   }
 
   // Elimina a `j` de los jugadores que tienen pendiente cantar flor
-  pub fn canto_flor(&mut self, j:&String) {
+  pub fn canto_flor(&mut self, j:usize) {
     // if j in self.sin_cantar:
     // self.sin_cantar.remove(j)
-    if let Some(pos) = self.sin_cantar.iter().position(|x| *x == *j) {
+    if let Some(pos) = self.sin_cantar.iter().position(|x| *x == j) {
       self.sin_cantar.remove(pos);
     }
   }
@@ -0,0 +1,48 @@
+use std::fmt;
+use crate::carta::{Carta};
+
+// errores que puede retornar el motor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+  // el jugador no esta en la partida
+  JugadorInexistente(String),
+  // valor o palo fuera de la baraja
+  CartaInvalida(String),
+  // el jugador no tiene esa carta en su manojo
+  CartaNoEnMano{jid: String, carta: Carta},
+  PartidaTerminada,
+  // la jugada no esta permitida; contiene el motivo
+  JugadaIlegal(String),
+  // el comando no respeta el formato `<jugador> <jugada>`
+  ComandoInvalido(String),
+  JugadoresRepetidos,
+  CantidadDeJugadoresInvalida,
+  PuntuacionInvalida(usize),
+  // el estado de la partida no es consistente
+  EstadoInvalido(String),
+  // la combinacion de reglas no tiene sentido
+  ReglasInvalidas(String),
+  // el snapshot es de otra version del formato
+  VersionIncompatible(u32),
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Error::JugadorInexistente(jid) => write!(f, "El jugador `{}` no existe", jid),
+      Error::CartaInvalida(msg) => write!(f, "Carta invalida: {}", msg),
+      Error::CartaNoEnMano{jid, carta} => write!(f, "{} no tiene el {}", jid, carta),
+      Error::PartidaTerminada => write!(f, "La partida ya termino"),
+      Error::JugadaIlegal(razon) => write!(f, "Jugada ilegal: {}", razon),
+      Error::ComandoInvalido(cmd) => write!(f, "Comando invalido: `{}`", cmd),
+      Error::JugadoresRepetidos => write!(f, "Hay jugadores repetidos"),
+      Error::CantidadDeJugadoresInvalida => write!(f, "La cantidad de jugadores es invalida"),
+      Error::PuntuacionInvalida(p) => write!(f, "La puntuacion {} no es valida", p),
+      Error::EstadoInvalido(msg) => write!(f, "Estado invalido: {}", msg),
+      Error::ReglasInvalidas(msg) => write!(f, "Reglas invalidas: {}", msg),
+      Error::VersionIncompatible(v) => write!(f, "La version {} del snapshot no es compatible", v),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use crate::carta::{Carta};
+use crate::reglas::{Reglas};
+
+// las cartas de una ronda: la muestra y el manojo de cada jugador, en el
+// mismo orden que `Ronda.manojos`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct Reparto {
+  pub muestra: Carta,
+  pub manojos: Vec<[Carta; 3]>,
+}
+
+// registro completo de una partida: su configuracion inicial, cada reparto
+// y cada jugada aplicada (en formato `Partida::cmd`), en orden
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+pub struct Historial {
+  pub puntuacion: usize,
+  pub azules: Vec<String>,
+  pub rojos: Vec<String>,
+  #[serde(default)]
+  pub reglas: Reglas,
+  pub verbose: bool,
+  pub repartos: Vec<Reparto>,
+  pub jugadas: Vec<String>,
+}
@@ -1,10 +1,12 @@
 use std::fmt::Debug;
+use serde::{Deserialize, Serialize};
 use crate::partida::{Partida};
 use crate::{enco, EstadoEnvite, NumMano, EstadoTruco, Resultado};
 use crate::carta::{Carta};
 use crate::equipo::{Equipo};
+use crate::error::{Error};
 
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum IJugadaId {
   JIdTirarCarta = 0,
   JIdEnvido = 1,
@@ -22,168 +24,188 @@ pub enum IJugadaId {
 }
 
 pub trait IJugada: Debug {
+  // la misma jugada como valor comparable/serializable
+  fn jugada(&self) -> Jugada;
   fn id(&self) -> IJugadaId;
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool);
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet>;
+  // la jugada en el formato de `Partida::cmd` pero sin el jugador (ej.
+  // `1 espada` o `envido`); el comando entero lo da `Partida::comando`
+  fn accion(&self) -> String;
+  // como `validar` y `hacer` pero en la ronda de `p`, sin tener en cuenta
+  // el pica-pica; `validar_en_ronda` puede asumir que el jugador existe
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error>;
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet>;
+
+  // `Err` con el motivo si la jugada no se puede hacer en `p`; durante un
+  // pica-pica se evalua en el duelo de su autor
+  fn validar(&self, p:&Partida) -> Result<(), Error> {
+    let jugada = self.jugada();
+    check_jugador(p, jugada.jid())?;
+    if let Some(res) = ok_en_duelo(p, &jugada) {
+      return res
+    }
+    self.validar_en_ronda(p)
+  }
+
+  // aplica la jugada sin registrarla en `p.historial` ni en lo que se
+  // puede deshacer (para eso usar `Partida::jugar`)
+  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+    if p.en_pica_pica() {
+      return p.hacer_en_duelo(self.jugada())
+    }
+    self.hacer_en_ronda(p)
+  }
+
+  // igual que `validar`, pero con el motivo como paquete para el jugador
+  // (si la partida es verbose)
+  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
+    let Err(err) = self.validar(p) else {
+      return (Vec::new(), true)
+    };
+    let mut pkts: Vec<enco::Packet> = Vec::new();
+    if p.verbose {
+      let jid = self.jugada().jid();
+      let destino = match p.ronda.manojos.get(jid) {
+        Some(m) => m.jugador.id.clone(),
+        None => jid.to_string(),
+      };
+      let msg = match err {
+        Error::JugadaIlegal(razon) => razon,
+        otro => otro.to_string(),
+      };
+      pkts.push(enco::Packet{
+        destination: vec![destino],
+        message: enco::Message(
+          enco::Content::Error { msg }
+        )
+      });
+    }
+    (pkts, false)
+  }
+}
+
+// el jugador `jid` debe existir en la partida
+fn check_jugador(p:&Partida, jid:usize) -> Result<(), Error> {
+  if jid < p.ronda.manojos.len() {
+    return Ok(())
+  }
+  Err(Error::JugadorInexistente(jid.to_string()))
+}
+
+// durante un pica-pica cada jugada se evalua en el duelo de su autor
+fn ok_en_duelo(p:&Partida, jugada:&Jugada) -> Option<Result<(), Error>> {
+  if !p.en_pica_pica() {
+    return None
+  }
+  if let Some((duelo, dix)) = p.get_duelo_mix(jugada.jid()) {
+    return Some(jugada.con_jid(dix).validar(duelo))
+  }
+  Some(Err(Error::JugadaIlegal(
+    "Tu duelo ya termino; hay que esperar a los demas".to_string())))
+}
+
+// el nombre del jugador de indice `jid` (para los paquetes)
+fn nombre(p:&Partida, jid:Option<usize>) -> String {
+  jid.map_or("", |ix| p.ronda.nombre(ix)).to_string()
 }
 
-#[derive(Debug)]
+// notifica a todos un error interno del motor
+pub(crate) fn error_pkts(p:&Partida, err: Error) -> Vec<enco::Packet> {
+  if !p.verbose {
+    return Vec::new()
+  }
+  vec![enco::Packet{
+    destination: vec!["ALL".to_string()],
+    message: enco::Message(
+      enco::Content::Error {
+        msg: err.to_string(),
+      }
+    )
+  }]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct TirarCarta {
-  pub jid: String,
+  pub jid: usize,
 	pub carta: Carta
 }
 
 impl IJugada for TirarCarta {
+  fn jugada(&self) -> Jugada {
+    Jugada::TirarCarta(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdTirarCarta
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
-
+  fn accion(&self) -> String {
+    format!("{} {}", self.carta.valor, self.carta.palo.to_string().to_lowercase())
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
     // checkeo si se fue al mazo
-    let no_se_fue_al_mazo = !p.ronda.manojo(&self.jid).se_fue_al_mazo;
+    let no_se_fue_al_mazo = !p.ronda.manojo(self.jid).se_fue_al_mazo;
     let ok = no_se_fue_al_mazo;
 
     if !ok {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tirar una carta porque ya te fuiste al mazo".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No es posible tirar una carta porque ya te fuiste al mazo".to_string()));
     }
 
     // esto es un tanto redundante porque es imposible que no sea su turno
     // (checkeado mas adelante) y que al mismo tiempo tenga algo para tirar
     // luego de haber jugado sus 3 cartas; aun asi lo dejo
     let ya_tiro_todas_sus_cartas = 
-      p.ronda.manojo(&self.jid).get_cant_cartas_tiradas() == 3;
+      p.ronda.manojo(self.jid).get_cant_cartas_tiradas() == 3;
     if ya_tiro_todas_sus_cartas {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tirar una carta porque ya las tiraste todas".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No es posible tirar una carta porque ya las tiraste todas".to_string()));
     }
 
     // checkeo flor en juego
     let envite_en_juego = p.ronda.envite.estado >= EstadoEnvite::Envido;
     if envite_en_juego {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tirar una carta ahora porque el envite esta en juego".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible tirar una carta ahora porque el envite esta en juego".to_string()));
     }
 
     // primero que nada: tiene esa carta?
-    // pide por un error, pero p.Manojo NO retorna error alguno !
-    let idx = p.ronda.manojo(&self.jid).get_carta_idx(&self.carta);
-    // todo: checkear que tiene esa carta
-    // if p.verbose {
-    //   pkts.push(enco::Packet{
-    //     destination: vec![self.jid.clone()],
-    //     message: enco::Message(
-    //       enco::Content::Error {
-    //         msg: err.Error(),
-    //       }
-    //     )
-    //   });
-    // }
-    // return (pkts, false);
+    let idx = match p.ronda.manojo(self.jid).get_carta_idx(&self.carta) {
+      Ok(idx) => idx,
+      Err(err) => {
+        return Err(err);
+      }
+    };
     
     // ya jugo esa carta?
-    let todavia_no_la_tiro = !p.ronda.manojo(&self.jid).tiradas[idx];
+    let todavia_no_la_tiro = !p.ronda.manojo(self.jid).tiradas[idx];
     if !todavia_no_la_tiro {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "Ya tiraste esa carta".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("Ya tiraste esa carta".to_string()));
     }
 
     // luego, era su turno?
     let era_su_turno = 
-      p.ronda.get_el_turno().jugador.id == self.jid;
+      p.ronda.turno == self.jid;
     if !era_su_turno {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No era su turno, no puede tirar la carta".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No era su turno, no puede tirar la carta".to_string()));
     }
 
     // checkeo si tiene flor
     let flor_habilitada = (p.ronda.envite.estado >= EstadoEnvite::NoCantadoAun && p.ronda.envite.estado <= EstadoEnvite::Flor) && p.ronda.mano_en_juego == NumMano::Primera;
-    let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
-    let no_canto_flor_aun = p.ronda.envite.no_canto_flor_aun(&self.jid);
+    let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
+    let no_canto_flor_aun = p.ronda.envite.no_canto_flor_aun(self.jid);
     let no_puede_tirar = flor_habilitada && tiene_flor && no_canto_flor_aun;
     if no_puede_tirar {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tirar una carta sin antes cantar la flor".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible tirar una carta sin antes cantar la flor".to_string()));
     }
 
     // cambio: ahora no puede tirar carta si el grito truco
     let truco_gritado = p.ronda.truco.estado.es_truco_respondible();
-    let uno_del_equipo_contrario_grito_truco = truco_gritado && p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
-    let yo_gite_el_truco = truco_gritado && self.jid == p.ronda.truco.cantado_por;
+    let uno_del_equipo_contrario_grito_truco = truco_gritado && p.ronda.equipo_de(p.ronda.truco.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
+    let yo_gite_el_truco = truco_gritado && Some(self.jid) == p.ronda.truco.cantado_por;
     let el_truco_es_respondible = truco_gritado && uno_del_equipo_contrario_grito_truco && !yo_gite_el_truco;
     if el_truco_es_respondible {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tirar una carta porque tu equipo debe responder la propuesta del truco".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible tirar una carta porque tu equipo debe responder la propuesta del truco".to_string()));
     }
     // ok
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -198,7 +220,7 @@ impl IJugada for TirarCarta {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::TirarCarta {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
             palo: self.carta.palo.to_string(),
             valor: self.carta.valor,
           }
@@ -206,11 +228,13 @@ impl IJugada for TirarCarta {
       });
     }
 
-    let idx = p.ronda.manojo(&self.jid).get_carta_idx(&self.carta);
-    p.tirar_carta(&self.jid, idx);
+    // `ok` ya verifico que la tiene
+    if let Ok(idx) = p.ronda.manojo(self.jid).get_carta_idx(&self.carta) {
+      p.tirar_carta(self.jid, idx);
+    }
 
     // era el ultimo en tirar de esta mano?
-	let era_el_ultimo_en_tirar = p.ronda.get_siguiente_habilitado(p.ronda.manojo(&self.jid)).is_none();
+	let era_el_ultimo_en_tirar = p.ronda.get_sig_habilitado(self.jid).is_none();
 	if era_el_ultimo_en_tirar {
 		// de ser asi tengo que checkear el resultado de la mano
 		let (empieza_nueva_ronda, mut res) = p.evaluar_mano();
@@ -226,7 +250,9 @@ impl IJugada for TirarCarta {
 			// actualizo el mano
 			p.ronda.mano_en_juego.inc();
 			// p.ronda.SetNextTurnoPosMano();
-			p.ronda.set_next_turno_pos_mano();
+			if let Err(err) = p.ronda.set_next_turno_pos_mano() {
+        pkts.append(&mut error_pkts(p, err));
+      }
       // lo envio
       if p.verbose {
         pkts.push(enco::Packet{
@@ -243,8 +269,10 @@ impl IJugada for TirarCarta {
 			if !p.terminada() {
 				// ahora se deberia de incrementar el mano
 				// y ser el turno de este
-				let sig_mano = p.ronda.get_sig_el_mano();
-				p.ronda.nueva_ronda(sig_mano); // todo: el tema es que cuando llama aca
+				if let Err(err) = p.nueva_ronda() {
+				  pkts.append(&mut error_pkts(p, err));
+				}
+				// todo: el tema es que cuando llama aca
 				// no manda mensaje de que arranco nueva ronda
 				// falso: el padre que llama a .EvaluarRonda tiene que fijarse si
 				// retorno true
@@ -278,7 +306,9 @@ impl IJugada for TirarCarta {
 
 		// el turno del siguiente queda dado por el ganador de esta
 	} else {
-		p.ronda.set_next_turno();
+		if let Err(err) = p.ronda.set_next_turno() {
+      pkts.append(&mut error_pkts(p, err));
+    }
     if p.verbose {
       pkts.push(enco::Packet{
         destination: vec!["ALL".to_string()],
@@ -295,52 +325,38 @@ impl IJugada for TirarCarta {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct TocarEnvido {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for TocarEnvido {
+  fn jugada(&self) -> Jugada {
+    Jugada::TocarEnvido(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdEnvido
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
+  fn accion(&self) -> String {
+    "envido".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
     // checkeo flor en juego
     let flor_en_juego = p.ronda.envite.estado >= EstadoEnvite::Flor;
     if flor_en_juego {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tocar el envido ahora porque la flor esta en juego".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible tocar el envido ahora porque la flor esta en juego".to_string()));
     }
-    let se_fue_al_mazo = p.ronda.manojo(&self.jid).se_fue_al_mazo;
+    let se_fue_al_mazo = p.ronda.manojo(self.jid).se_fue_al_mazo;
     let es_primera_mano = p.ronda.mano_en_juego == NumMano::Primera;
-    let es_su_turno = p.ronda.get_el_turno().jugador.id == self.jid;
-    let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
+    let es_su_turno = p.ronda.turno == self.jid;
+    let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
     let envido_habilitado = p.ronda.envite.estado == EstadoEnvite::NoCantadoAun || p.ronda.envite.estado == EstadoEnvite::Envido;
     
     if !envido_habilitado {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tocar envido ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible tocar envido ahora".to_string()));
     }
 
-    let es_del_equipo_contrario = p.ronda.envite.estado == EstadoEnvite::NoCantadoAun || p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    let es_del_equipo_contrario = p.ronda.envite.estado == EstadoEnvite::NoCantadoAun || p.ronda.equipo_de(p.ronda.envite.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
     let ya_estabamos_en_envido = p.ronda.envite.estado == EstadoEnvite::Envido;
     // apuestaSaturada = p.ronda.envite.Puntaje >= p.CalcPtsFalta()
     let apuesta_saturada = p.ronda.envite.puntaje >= 4;
@@ -355,24 +371,14 @@ impl IJugada for TocarEnvido {
     let ok = !se_fue_al_mazo && (envido_habilitado && es_primera_mano && !tiene_flor && es_del_equipo_contrario) && puede_tocar_envido && !apuesta_saturada;
 
     if !ok {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible cantar 'Envido'".to_string(),
-            }
-          )
-        });
-      }  
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible cantar 'Envido'".to_string()));
     }
     
     // ok
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -399,7 +405,7 @@ impl IJugada for TocarEnvido {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::ElEnvidoEstaPrimero {
-              autor: self.jid.clone(),
+              autor: p.ronda.nombre(self.jid).to_string(),
             }
           )
         });
@@ -411,19 +417,19 @@ impl IJugada for TocarEnvido {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::TocarEnvido {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
     }
 
     // ahora checkeo si alguien tiene flor
-    let hay_flor = p.ronda.envite.sin_cantar.len() > 0;
+    let hay_flor = !p.ronda.envite.sin_cantar.is_empty();
     if hay_flor {
       // todo: deberia ir al estado magico en el que espera
       // solo por jugadas de tipo flor-related
       // lo mismo para el real-envido; falta-envido
-      let jid = p.ronda.envite.sin_cantar[0].clone();
+      let jid = p.ronda.envite.sin_cantar[0];
 
       // todo
       let siguiente_jugada = CantarFlor{ jid };
@@ -431,7 +437,7 @@ impl IJugada for TocarEnvido {
       pkts.append(&mut res);
 
     } else {
-      p.tocar_envido(&self.jid);
+      p.tocar_envido(self.jid);
     }
 
     pkts
@@ -464,52 +470,38 @@ impl TocarEnvido {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct TocarRealEnvido {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for TocarRealEnvido {
+  fn jugada(&self) -> Jugada {
+    Jugada::TocarRealEnvido(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdRealEnvido
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
+  fn accion(&self) -> String {
+    "real-envido".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
     // checkeo flor en juego
     let flor_en_juego = p.ronda.envite.estado >= EstadoEnvite::Flor;
     if flor_en_juego {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tocar real envido ahora porque la flor esta en juego".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible tocar real envido ahora porque la flor esta en juego".to_string()));
     }
-    let se_fue_al_mazo = p.ronda.manojo(&self.jid).se_fue_al_mazo;
+    let se_fue_al_mazo = p.ronda.manojo(self.jid).se_fue_al_mazo;
     let es_primera_mano = p.ronda.mano_en_juego == NumMano::Primera;
-    let es_su_turno = p.ronda.get_el_turno().jugador.id == self.jid;
-    let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
+    let es_su_turno = p.ronda.turno == self.jid;
+    let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
     let real_envido_habilitado = p.ronda.envite.estado == EstadoEnvite::NoCantadoAun || p.ronda.envite.estado == EstadoEnvite::Envido;
 
     if !real_envido_habilitado {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tocar real-envido ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible tocar real-envido ahora".to_string()));
     }
 
-    let es_del_equipo_contrario = p.ronda.envite.estado == EstadoEnvite::NoCantadoAun || p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    let es_del_equipo_contrario = p.ronda.envite.estado == EstadoEnvite::NoCantadoAun || p.ronda.equipo_de(p.ronda.envite.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
     let ya_estabamos_en_envido = p.ronda.envite.estado == EstadoEnvite::Envido;
     let truco_no_cantado = p.ronda.truco.estado == EstadoTruco::NoCantado;
 
@@ -521,24 +513,14 @@ impl IJugada for TocarRealEnvido {
     let ok = !se_fue_al_mazo && (real_envido_habilitado && es_primera_mano && !tiene_flor && es_del_equipo_contrario) && puede_tocar_real_envido;
 
     if !ok {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible cantar 'Real Envido'".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible cantar 'Real Envido'".to_string()));
     }
 
     // ok
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -565,7 +547,7 @@ impl IJugada for TocarRealEnvido {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::ElEnvidoEstaPrimero {
-              autor: self.jid.clone(),
+              autor: p.ronda.nombre(self.jid).to_string(),
             }
           )
         });
@@ -577,19 +559,19 @@ impl IJugada for TocarRealEnvido {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::TocarRealEnvido {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
     }
 
-    p.tocar_real_envido(&self.jid);
+    p.tocar_real_envido(self.jid);
   
     // ahora checkeo si alguien tiene flor
-    let hay_flor = p.ronda.envite.sin_cantar.len() > 0;
+    let hay_flor = !p.ronda.envite.sin_cantar.is_empty();
   
     if hay_flor {
-      let jid = p.ronda.envite.sin_cantar[0].clone();
+      let jid = p.ronda.envite.sin_cantar[0];
       let siguiente_jugada = CantarFlor{ jid };
       let mut res = siguiente_jugada.hacer(p);
       pkts.append(&mut res);
@@ -599,54 +581,39 @@ impl IJugada for TocarRealEnvido {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct TocarFaltaEnvido {
-  pub jid: String,
+  pub jid: usize,
 }
 
 impl IJugada for TocarFaltaEnvido {
+  fn jugada(&self) -> Jugada {
+    Jugada::TocarFaltaEnvido(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdFaltaEnvido
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
+  fn accion(&self) -> String {
+    "falta-envido".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
     // ok
     // checkeo flor en juego
     let flor_en_juego = p.ronda.envite.estado >= EstadoEnvite::Flor;
     if flor_en_juego {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tocar falta envido ahora porque la flor esta en juego".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible tocar falta envido ahora porque la flor esta en juego".to_string()));
     }
-    let se_fue_al_mazo = p.ronda.manojo(&self.jid).se_fue_al_mazo;
-    let es_su_turno = p.ronda.get_el_turno().jugador.id == self.jid;
+    let se_fue_al_mazo = p.ronda.manojo(self.jid).se_fue_al_mazo;
+    let es_su_turno = p.ronda.turno == self.jid;
     let es_primera_mano = p.ronda.mano_en_juego == NumMano::Primera;
-    let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
+    let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
     let falta_envido_habilitado = p.ronda.envite.estado >= EstadoEnvite::NoCantadoAun && p.ronda.envite.estado < EstadoEnvite::FaltaEnvido;
 
     if !falta_envido_habilitado {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible tocar real-envido ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible tocar real-envido ahora".to_string()));
     }
 
-    let es_del_equipo_contrario = p.ronda.envite.estado == EstadoEnvite::NoCantadoAun || p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    let es_del_equipo_contrario = p.ronda.envite.estado == EstadoEnvite::NoCantadoAun || p.ronda.equipo_de(p.ronda.envite.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
     let ya_estabamos_en_envido = p.ronda.envite.estado >= EstadoEnvite::Envido;
     let truco_no_cantado = p.ronda.truco.estado == EstadoTruco::NoCantado;
 
@@ -658,23 +625,13 @@ impl IJugada for TocarFaltaEnvido {
     let ok = !se_fue_al_mazo && (falta_envido_habilitado && es_primera_mano && !tiene_flor && es_del_equipo_contrario) && puede_tocar_falta_envido;
 
     if !ok {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible cantar 'Falta Envido'".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible cantar 'Falta Envido'".to_string()));
     }
 
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -701,7 +658,7 @@ impl IJugada for TocarFaltaEnvido {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::ElEnvidoEstaPrimero {
-              autor: self.jid.clone(),
+              autor: p.ronda.nombre(self.jid).to_string(),
             }
           )
         });
@@ -713,18 +670,18 @@ impl IJugada for TocarFaltaEnvido {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::TocarFaltaEnvido {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
     }
 
-    p.tocar_falta_envido(&self.jid);
+    p.tocar_falta_envido(self.jid);
 
     // ahora checkeo si alguien tiene flor
-    let hay_flor = p.ronda.envite.sin_cantar.len() > 0;
+    let hay_flor = !p.ronda.envite.sin_cantar.is_empty();
     if hay_flor {
-      let jid = p.ronda.envite.sin_cantar[0].clone();
+      let jid = p.ronda.envite.sin_cantar[0];
       let siguiente_jugada = CantarFlor{ jid };
       let mut res = siguiente_jugada.hacer(p);
       pkts.append(&mut res);
@@ -772,9 +729,9 @@ impl TocarFaltaEnvido {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct CantarFlor {
-  pub jid: String,
+  pub jid: usize,
 }
 
 /*
@@ -798,7 +755,7 @@ fn eval_flor(p: &mut Partida) -> Vec<enco::Packet> {
   let mut pkts: Vec<enco::Packet> = Vec::new();
 
 	let flor_en_juego = p.ronda.envite.estado >= EstadoEnvite::Flor;
-	let todos_los_jugadores_con_flor_cantaron = p.ronda.envite.sin_cantar.len() == 0;
+	let todos_los_jugadores_con_flor_cantaron = p.ronda.envite.sin_cantar.is_empty();
 	let ok = todos_los_jugadores_con_flor_cantaron && flor_en_juego;
 	if !ok {
 		return pkts
@@ -806,7 +763,7 @@ fn eval_flor(p: &mut Partida) -> Vec<enco::Packet> {
 
   // cual es la flor ganadora?
 	// empieza cantando el autor del envite no el que "quizo"
-	let autor_idx = p.ronda.mixs[&p.ronda.envite.cantado_por];
+	let autor_idx = p.ronda.envite.cantado_por.unwrap_or(p.ronda.el_mano);
 
   let equipo_ganador: Equipo;
   let ganador: String;
@@ -834,7 +791,7 @@ fn eval_flor(p: &mut Partida) -> Vec<enco::Packet> {
       message: enco::Message(
         enco::Content::SumaPts { 
           autor: ganador, 
-          razon: razon, 
+          razon, 
           pts: puntos_asumar
         }
       )
@@ -848,37 +805,32 @@ fn eval_flor(p: &mut Partida) -> Vec<enco::Packet> {
 }
 
 impl IJugada for CantarFlor {
+  fn jugada(&self) -> Jugada {
+    Jugada::CantarFlor(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdFlor
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
+  fn accion(&self) -> String {
+    "flor".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
     // manojo dice que puede cantar flor;
     // es esto verdad?
-    let se_fue_al_mazo = p.ronda.manojo(&self.jid).se_fue_al_mazo;
+    let se_fue_al_mazo = p.ronda.manojo(self.jid).se_fue_al_mazo;
     let flor_habilitada = (p.ronda.envite.estado >= EstadoEnvite::NoCantadoAun) && p.ronda.mano_en_juego == NumMano::Primera;
-    let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
-    let no_canto_flor_aun = p.ronda.envite.no_canto_flor_aun(&self.jid);
+    let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
+    let no_canto_flor_aun = p.ronda.envite.no_canto_flor_aun(self.jid);
     
     let ok = !se_fue_al_mazo && flor_habilitada && tiene_flor && no_canto_flor_aun;
 
     if !ok {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible cantar flor".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible cantar flor".to_string()));
     }
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -893,7 +845,7 @@ impl IJugada for CantarFlor {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::CantarContraFlor {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
@@ -911,7 +863,7 @@ impl IJugada for CantarFlor {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::ElEnvidoEstaPrimero {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
@@ -920,12 +872,12 @@ impl IJugada for CantarFlor {
 
 
     // y me elimino de los que no-cantaron
-    p.ronda.envite.canto_flor(&self.jid);
-    p.cantar_flor(&self.jid);
+    p.ronda.envite.canto_flor(self.jid);
+    p.cantar_flor(self.jid);
 
     // es el ultimo en cantar flor que faltaba?
     // o simplemente es el unico que tiene flor (caso particular)
-    let todos_los_jugadores_con_flor_cantaron = p.ronda.envite.sin_cantar.len() == 0;
+    let todos_los_jugadores_con_flor_cantaron = p.ronda.envite.sin_cantar.is_empty();
     if todos_los_jugadores_con_flor_cantaron {
       let mut res = eval_flor(p);
       pkts.append(&mut res);
@@ -936,8 +888,8 @@ impl IJugada for CantarFlor {
       let mut solo_los_de_su_equipo_tienen_flor = true;
 
       for jid in p.ronda.envite.jugadores_con_flor.iter() {
-        let manojo = p.ronda.manojo(jid);
-        if manojo.jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo {
+        let manojo = p.ronda.manojo(*jid);
+        if manojo.jugador.equipo != p.ronda.manojo(self.jid).jugador.equipo {
           solo_los_de_su_equipo_tienen_flor = false;
           break
         }
@@ -947,7 +899,7 @@ impl IJugada for CantarFlor {
         // los quiero llamar a todos, pero no quiero Hacer llamadas al pedo
         // entonces: llamo al primero sin cantar, y que este llame al proximo
         // y que el proximo llame al siguiente, y asi...
-        let jid = p.ronda.envite.sin_cantar[0].clone();
+        let jid = p.ronda.envite.sin_cantar[0];
         // j = p.ronda.manojo(jid);
         let siguiente_jugada = CantarFlor{ jid };
         let mut res = siguiente_jugada.hacer(p);
@@ -959,39 +911,35 @@ impl IJugada for CantarFlor {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct CantarContraFlor {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for CantarContraFlor {
+  fn jugada(&self) -> Jugada {
+    Jugada::CantarContraFlor(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdContraFlor
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
-    let se_fue_al_mazo = p.ronda.manojo(&self.jid).se_fue_al_mazo;
+  fn accion(&self) -> String {
+    "contra-flor".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
+    let se_fue_al_mazo = p.ronda.manojo(self.jid).se_fue_al_mazo;
     let contra_flor_habilitada = p.ronda.envite.estado == EstadoEnvite::Flor && p.ronda.mano_en_juego == NumMano::Primera;
-    let es_del_equipo_contrario = contra_flor_habilitada && p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
-    let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
-    let no_canto_flor_aun = p.ronda.envite.no_canto_flor_aun(&self.jid);
+    let es_del_equipo_contrario = contra_flor_habilitada && p.ronda.equipo_de(p.ronda.envite.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
+    let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
+    let no_canto_flor_aun = p.ronda.envite.no_canto_flor_aun(self.jid);
     let ok = !se_fue_al_mazo && contra_flor_habilitada && tiene_flor && es_del_equipo_contrario && no_canto_flor_aun;
     if !ok {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible cantar contra flor".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible cantar contra flor".to_string()));
     }
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -1006,55 +954,51 @@ impl IJugada for CantarContraFlor {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::CantarContraFlor {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
     }
 
-    p.cantar_contra_flor(&self.jid);
+    p.cantar_contra_flor(self.jid);
     // y ahora tengo que esperar por la respuesta de la nueva
     // propuesta de todos menos de el que canto la contraflor
     // restauro la copia
-    p.ronda.envite.canto_flor(&self.jid);
+    p.ronda.envite.canto_flor(self.jid);
 
     pkts
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct CantarContraFlorAlResto {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for CantarContraFlorAlResto {
+  fn jugada(&self) -> Jugada {
+    Jugada::CantarContraFlorAlResto(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdContraFlorAlResto
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
-    let se_fue_al_mazo = p.ronda.manojo(&self.jid).se_fue_al_mazo;
+  fn accion(&self) -> String {
+    "contra-flor-al-resto".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
+    let se_fue_al_mazo = p.ronda.manojo(self.jid).se_fue_al_mazo;
     let contra_flor_habilitada = (p.ronda.envite.estado == EstadoEnvite::Flor || p.ronda.envite.estado == EstadoEnvite::ContraFlor) && p.ronda.mano_en_juego == NumMano::Primera;
-    let es_del_equipo_contrario = contra_flor_habilitada && p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
-    let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
-    let no_canto_flor_aun = p.ronda.envite.no_canto_flor_aun(&self.jid);
-    let ok = !se_fue_al_mazo && contra_flor_habilitada && tiene_flor && es_del_equipo_contrario && no_canto_flor_aun;
+    let es_del_equipo_contrario = contra_flor_habilitada && p.ronda.equipo_de(p.ronda.envite.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
+    let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
+    let no_canto_flor_aun = p.ronda.envite.no_canto_flor_aun(self.jid);
+    let ok = p.ronda.reglas.contraflor_al_resto && !se_fue_al_mazo && contra_flor_habilitada && tiene_flor && es_del_equipo_contrario && no_canto_flor_aun;
     if !ok {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible cantar contra flor al resto".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible cantar contra flor al resto".to_string()));
     }
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -1068,60 +1012,56 @@ impl IJugada for CantarContraFlorAlResto {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::CantarContraFlorAlResto {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
     }
 
-    p.cantar_contra_flor_al_resto(&self.jid);
+    p.cantar_contra_flor_al_resto(self.jid);
     // y ahora tengo que esperar por la respuesta de la nueva
     // propuesta de todos menos de el que canto la contraflor
     // restauro la copia
-    p.ronda.envite.canto_flor(&self.jid);
+    p.ronda.envite.canto_flor(self.jid);
 
     pkts
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct GritarTruco {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for GritarTruco {
+  fn jugada(&self) -> Jugada {
+    Jugada::GritarTruco(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdTruco
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
+  fn accion(&self) -> String {
+    "truco".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
     // checkeos:
-    let no_se_fue_al_mazo = !p.ronda.manojo(&self.jid).se_fue_al_mazo;
+    let no_se_fue_al_mazo = !p.ronda.manojo(self.jid).se_fue_al_mazo;
     let no_se_esta_jugando_el_envite = p.ronda.envite.estado <= EstadoEnvite::NoCantadoAun;
 
-    let yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto = p.ronda.hay_equipo_sin_cantar(p.ronda.manojo(&self.jid).jugador.equipo);
+    let yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto = p.ronda.hay_equipo_sin_cantar(p.ronda.manojo(self.jid).jugador.equipo);
 
     let la_flor_esta_primero = yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto;
     let truco_no_se_jugo_aun = p.ronda.truco.estado == EstadoTruco::NoCantado;
-    let es_su_turno = p.ronda.get_el_turno().jugador.id == self.jid;
+    let es_su_turno = p.ronda.turno == self.jid;
     let truco_habilitado = no_se_fue_al_mazo && truco_no_se_jugo_aun && no_se_esta_jugando_el_envite && !la_flor_esta_primero && es_su_turno;
 
     if !truco_habilitado {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible cantar truco ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false)
+      return Err(Error::JugadaIlegal("No es posible cantar truco ahora".to_string()));
     }
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -1135,65 +1075,60 @@ impl IJugada for GritarTruco {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::GritarTruco {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
     }
   
-    p.gritar_truco(&self.jid);
+    p.gritar_truco(self.jid);
 
     pkts
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct GritarReTruco {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for GritarReTruco {
+  fn jugada(&self) -> Jugada {
+    Jugada::GritarReTruco(self.clone())
+  }
   fn id(&self) -> IJugadaId {
-    IJugadaId::JIdTirarCarta
+    IJugadaId::JIdReTruco
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
-
-    let no_se_fue_al_mazo = !p.ronda.manojo(&self.jid).se_fue_al_mazo;
+  fn accion(&self) -> String {
+    "re-truco".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
+    let no_se_fue_al_mazo = !p.ronda.manojo(self.jid).se_fue_al_mazo;
     let no_se_esta_jugando_el_envite = p.ronda.envite.estado <= EstadoEnvite::NoCantadoAun;
-    let yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto = p.ronda.hay_equipo_sin_cantar(p.ronda.manojo(&self.jid).jugador.equipo);
+    let yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto = p.ronda.hay_equipo_sin_cantar(p.ronda.manojo(self.jid).jugador.equipo);
     let la_flor_esta_primero = yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto;
 
     // CASO I:
     let truco_gritado = p.ronda.truco.estado == EstadoTruco::Truco;
-    let uno_del_equipo_contrario_grito_truco = truco_gritado && p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    let uno_del_equipo_contrario_grito_truco = truco_gritado && p.ronda.equipo_de(p.ronda.truco.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
     let caso_i = truco_gritado && uno_del_equipo_contrario_grito_truco;
 
     // CASO II:
     let truco_ya_querido = p.ronda.truco.estado == EstadoTruco::TrucoQuerido;
-    let uno_de_mi_equipo_quizo = truco_ya_querido && p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo == p.ronda.manojo(&self.jid).jugador.equipo;
-    // esTurnoDeMiEquipo = p.ronda.get_el_turno().jugador.equipo == p.ronda.manojo(&self.jid).jugador.equipo;
+    let uno_de_mi_equipo_quizo = truco_ya_querido && p.ronda.equipo_de(p.ronda.truco.cantado_por) == Some(p.ronda.manojo(self.jid).jugador.equipo);
+    // esTurnoDeMiEquipo = p.ronda.get_el_turno().jugador.equipo == p.ronda.manojo(self.jid).jugador.equipo;
     let caso_ii = truco_ya_querido && uno_de_mi_equipo_quizo; // && esTurnoDeMiEquipo;
 
     let re_truco_habilitado = no_se_fue_al_mazo && no_se_esta_jugando_el_envite && (caso_i || caso_ii) && !la_flor_esta_primero;
 
     if !re_truco_habilitado {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible cantar re-truco ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No es posible cantar re-truco ahora".to_string()));
     }
 
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -1207,60 +1142,56 @@ impl IJugada for GritarReTruco {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::GritarReTruco {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
     }
   
-    p.gritar_retruco(&self.jid);
+    p.gritar_retruco(self.jid);
 
     pkts
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct GritarVale4 {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for GritarVale4 {
+  fn jugada(&self) -> Jugada {
+    Jugada::GritarVale4(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdVale4
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
-    let no_se_fue_al_mazo = !p.ronda.manojo(&self.jid).se_fue_al_mazo;
+  fn accion(&self) -> String {
+    "vale-4".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
+    let no_se_fue_al_mazo = !p.ronda.manojo(self.jid).se_fue_al_mazo;
     let no_se_esta_jugando_el_envite = p.ronda.envite.estado <= EstadoEnvite::NoCantadoAun;
-    let yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto = p.ronda.hay_equipo_sin_cantar(p.ronda.manojo(&self.jid).jugador.equipo);
+    let yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto = p.ronda.hay_equipo_sin_cantar(p.ronda.manojo(self.jid).jugador.equipo);
     let la_flor_esta_primero = yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto;
     // CASO I:
     let re_truco_gritado = p.ronda.truco.estado == EstadoTruco::ReTruco;
     // para eviat el nil primero checkeo que haya sido gritado reTrucoGritado &&
-    let uno_del_equipo_contrario_grito_re_truco = re_truco_gritado && p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    let uno_del_equipo_contrario_grito_re_truco = re_truco_gritado && p.ronda.equipo_de(p.ronda.truco.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
     let caso_i = re_truco_gritado && uno_del_equipo_contrario_grito_re_truco;
     // CASO I:
     let retruco_ya_querido = p.ronda.truco.estado == EstadoTruco::ReTrucoQuerido;
     // para eviat el nil primero checkeo que haya sido gritado reTrucoGritado &&
-    let su_equipotiene_el_quiero = retruco_ya_querido && p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo == p.ronda.manojo(&self.jid).jugador.equipo;
+    let su_equipotiene_el_quiero = retruco_ya_querido && p.ronda.equipo_de(p.ronda.truco.cantado_por) == Some(p.ronda.manojo(self.jid).jugador.equipo);
     let caso_ii = retruco_ya_querido && su_equipotiene_el_quiero;
     let vale4_habilitado = no_se_fue_al_mazo && (caso_i || caso_ii) && no_se_esta_jugando_el_envite && !la_flor_esta_primero;
     if !vale4_habilitado {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible cantar vale-4 ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No es posible cantar vale-4 ahora".to_string()));
     }
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -1274,41 +1205,37 @@ impl IJugada for GritarVale4 {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::GritarVale4 {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
     }
   
-    p.gritar_vale4(&self.jid);
+    p.gritar_vale4(self.jid);
 
     pkts
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct ResponderQuiero {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for ResponderQuiero {
+  fn jugada(&self) -> Jugada {
+    Jugada::ResponderQuiero(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdQuiero
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
-    let se_fue_al_mazo = p.ronda.manojo(&self.jid).se_fue_al_mazo;
+  fn accion(&self) -> String {
+    "quiero".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
+    let se_fue_al_mazo = p.ronda.manojo(self.jid).se_fue_al_mazo;
     if se_fue_al_mazo {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "Te fuiste al mazo; no podes Hacer esta jugada".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("Te fuiste al mazo; no podes Hacer esta jugada".to_string()));
     }
 
     // checkeo flor en juego
@@ -1324,33 +1251,13 @@ impl IJugada for ResponderQuiero {
 
     let flor_en_juego = p.ronda.envite.estado == EstadoEnvite::Flor;
     if flor_en_juego {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible responder quiero ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No es posible responder quiero ahora".to_string()));
     }
 
     let no_han_cantado_la_flor_aun = p.ronda.envite.estado < EstadoEnvite::Flor;
-    let yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto = p.ronda.hay_equipo_sin_cantar(p.ronda.manojo(&self.jid).jugador.equipo);
+    let yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto = p.ronda.hay_equipo_sin_cantar(p.ronda.manojo(self.jid).jugador.equipo);
     if no_han_cantado_la_flor_aun && yo_ouno_de_mis_compas_tiene_flor_yaun_no_canto {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible responder 'quiero' porque alguien con flor no ha cantado aun".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No es posible responder 'quiero' porque alguien con flor no ha cantado aun".to_string()));
     }
     // se acepta una respuesta 'quiero' solo cuando:
     // - CASO I: se toco un envite+ (con autor del equipo contario)
@@ -1359,64 +1266,34 @@ impl IJugada for ResponderQuiero {
 
     let el_envido_es_respondible = p.ronda.envite.estado >= EstadoEnvite::Envido && p.ronda.envite.estado <= EstadoEnvite::FaltaEnvido;
     // ojo: solo a la contraflor+ se le puede decir quiero; a la flor sola no
-    let la_contra_flor_es_respondible = p.ronda.envite.estado >= EstadoEnvite::ContraFlor && p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
-    let el_truco_es_respondible = p.ronda.truco.estado.es_truco_respondible() && p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    let la_contra_flor_es_respondible = p.ronda.envite.estado >= EstadoEnvite::ContraFlor && p.ronda.equipo_de(p.ronda.envite.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
+    let el_truco_es_respondible = p.ronda.truco.estado.es_truco_respondible() && p.ronda.equipo_de(p.ronda.truco.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
 
     let ok = el_envido_es_respondible || la_contra_flor_es_respondible || el_truco_es_respondible;
     if !ok {
       // si no, esta respondiendo al pedo
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No hay nada 'que querer'; ya que: el estado del envido no es 'envido' (o mayor) y el estado del truco no es 'truco' (o mayor) o bien fue cantado por uno de su equipo".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No hay nada 'que querer'; ya que: el estado del envido no es 'envido' (o mayor) y el estado del truco no es 'truco' (o mayor) o bien fue cantado por uno de su equipo".to_string()));
     }
 
     if el_envido_es_respondible {
-      let es_del_equipo_contrario = p.ronda.manojo(&self.jid).jugador.equipo != p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo;
+      let es_del_equipo_contrario = Some(p.ronda.manojo(self.jid).jugador.equipo) != p.ronda.equipo_de(p.ronda.envite.cantado_por);
       if !es_del_equipo_contrario {
-        if p.verbose {
-          pkts.push(enco::Packet{
-            destination: vec![self.jid.clone()],
-            message: enco::Message(
-              enco::Content::Error {
-                msg: "La jugada no es valida".to_string(),
-              }
-            )
-          });
-        }
-        return (pkts, false);
+        return Err(Error::JugadaIlegal("La jugada no es valida".to_string()));
       }
 
     } else if la_contra_flor_es_respondible {
       // tengo que verificar si efectivamente tiene flor
-      let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
-      let es_del_equipo_contrario = p.ronda.manojo(&self.jid).jugador.equipo != p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo;
+      let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
+      let es_del_equipo_contrario = Some(p.ronda.manojo(self.jid).jugador.equipo) != p.ronda.equipo_de(p.ronda.envite.cantado_por);
       let ok = tiene_flor && es_del_equipo_contrario;
       if !ok {
-        if p.verbose {
-          pkts.push(enco::Packet{
-            destination: vec![self.jid.clone()],
-            message: enco::Message(
-              enco::Content::Error {
-                msg: "La jugada no es valida".to_string(),
-              }
-            )
-          });
-        }
-        return (pkts, false);
+        return Err(Error::JugadaIlegal("La jugada no es valida".to_string()));
       }
     }
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -1432,8 +1309,8 @@ impl IJugada for ResponderQuiero {
 
     let el_envido_es_respondible = p.ronda.envite.estado >= EstadoEnvite::Envido && p.ronda.envite.estado <= EstadoEnvite::FaltaEnvido;
     // ojo: solo a la contraflor+ se le puede decir quiero; a la flor sola no
-    let la_contra_flor_es_respondible = p.ronda.envite.estado >= EstadoEnvite::ContraFlor && p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
-    let el_truco_es_respondible = p.ronda.truco.estado.es_truco_respondible() && p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    let la_contra_flor_es_respondible = p.ronda.envite.estado >= EstadoEnvite::ContraFlor && p.ronda.equipo_de(p.ronda.envite.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
+    let el_truco_es_respondible = p.ronda.truco.estado.es_truco_respondible() && p.ronda.equipo_de(p.ronda.truco.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
 
     if el_envido_es_respondible {
       if p.verbose {
@@ -1441,21 +1318,21 @@ impl IJugada for ResponderQuiero {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::QuieroEnvite {
-              autor: self.jid.clone(),
+              autor: p.ronda.nombre(self.jid).to_string(),
             }
           )
         });
       }
 
       if p.ronda.envite.estado == EstadoEnvite::FaltaEnvido {
-        let mut res = TocarFaltaEnvido{jid: self.jid.clone()}.eval(p);
+        let mut res = TocarFaltaEnvido{jid: self.jid}.eval(p);
         pkts.append(&mut res);
         return pkts;
       }
       // si no, era envido/real-envido o cualquier
       // combinacion valida de ellos
 
-      let mut res = TocarEnvido{jid: self.jid.clone()}.eval(p);
+      let mut res = TocarEnvido{jid: self.jid}.eval(p);
       pkts.append(&mut res);
       return pkts;
 
@@ -1466,14 +1343,14 @@ impl IJugada for ResponderQuiero {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::QuieroEnvite {
-              autor: self.jid.clone(),
+              autor: p.ronda.nombre(self.jid).to_string(),
             }
           )
         });
       }
 
       // empieza cantando el autor del envite no el que "quizo"
-      let autor_idx = p.ronda.mixs[&p.ronda.envite.cantado_por];
+      let autor_idx = p.ronda.envite.cantado_por.unwrap_or(p.ronda.el_mano);
       let equipo_ganador: Equipo;
       let ganador: String;
       {
@@ -1527,42 +1404,38 @@ impl IJugada for ResponderQuiero {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::QuieroTruco {
-              autor: self.jid.clone(),
+              autor: p.ronda.nombre(self.jid).to_string(),
             }
           )
         });
       }
-      p.querer_truco(&self.jid)
+      p.querer_truco(self.jid)
     }
 
     pkts
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct ResponderNoQuiero {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for ResponderNoQuiero {
+  fn jugada(&self) -> Jugada {
+    Jugada::ResponderNoQuiero(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdNoQuiero
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
+  fn accion(&self) -> String {
+    "no-quiero".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
     
-    let se_fue_al_mazo = p.ronda.manojo(&self.jid).se_fue_al_mazo;
+    let se_fue_al_mazo = p.ronda.manojo(self.jid).se_fue_al_mazo;
     if se_fue_al_mazo {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "Te fuiste al mazo; no podes Hacer esta jugada".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("Te fuiste al mazo; no podes Hacer esta jugada".to_string()));
     }
 
     // checkeo flor en juego
@@ -1571,7 +1444,7 @@ impl IJugada for ResponderNoQuiero {
     // FALSO porque el no quiero lo estoy contando como un "con flor me achico"
     // todo: agregar la jugada: "con flor me achico" y editar la variale:
     // AHORA:
-    // laFlorEsRespondible = p.ronda.Flor >= EstadoEnvite::Flor && p.ronda.manojo[p.ronda.envite.cantado_por].jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    // laFlorEsRespondible = p.ronda.Flor >= EstadoEnvite::Flor && p.ronda.manojo[p.ronda.envite.cantado_por].jugador.equipo != p.ronda.manojo(self.jid).jugador.equipo;
     // LUEGO DE AGREGAR LA JUGADA "con flor me achico"
     // laFlorEsRespondible = p.ronda.Flor > EstadoEnvite::Flor;
     // FALSO ---> directamente se va la posibilidad de reponderle
@@ -1582,9 +1455,9 @@ impl IJugada for ResponderNoQuiero {
     // - CASO II: se grito el truco (o similar)
     // en caso contrario, es incorrecto -> error
 
-    let el_envido_es_respondible = (p.ronda.envite.estado >= EstadoEnvite::Envido && p.ronda.envite.estado <= EstadoEnvite::FaltaEnvido) && p.ronda.envite.cantado_por != self.jid;
-    let la_flor_es_respondible = p.ronda.envite.estado >= EstadoEnvite::Flor && p.ronda.envite.cantado_por != self.jid;
-    let el_truco_es_respondible = p.ronda.truco.estado.es_truco_respondible() && p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    let el_envido_es_respondible = (p.ronda.envite.estado >= EstadoEnvite::Envido && p.ronda.envite.estado <= EstadoEnvite::FaltaEnvido) && p.ronda.envite.cantado_por != Some(self.jid);
+    let la_flor_es_respondible = p.ronda.envite.estado >= EstadoEnvite::Flor && p.ronda.envite.cantado_por != Some(self.jid);
+    let el_truco_es_respondible = p.ronda.truco.estado.es_truco_respondible() && p.ronda.equipo_de(p.ronda.truco.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
 
     let ok = el_envido_es_respondible || la_flor_es_respondible || el_truco_es_respondible;
 
@@ -1595,58 +1468,28 @@ impl IJugada for ResponderNoQuiero {
           "{} esta respondiendo al pedo; no hay nada respondible",
           self.jid.clone()
         );
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: err,
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal(err));
     }
 
     if el_envido_es_respondible {
-      let es_del_equipo_contrario = p.ronda.manojo(&self.jid).jugador.equipo != p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo;
+      let es_del_equipo_contrario = Some(p.ronda.manojo(self.jid).jugador.equipo) != p.ronda.equipo_de(p.ronda.envite.cantado_por);
       if !es_del_equipo_contrario {
-        if p.verbose {
-          pkts.push(enco::Packet{
-            destination: vec![self.jid.clone()],
-            message: enco::Message(
-              enco::Content::Error {
-                msg: "La jugada no es valida".to_string(),
-              }
-            )
-          });
-        }
-        return (pkts, false);
+        return Err(Error::JugadaIlegal("La jugada no es valida".to_string()));
       }
     } else if la_flor_es_respondible {
       // tengo que verificar si efectivamente tiene flor
-      let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
-      let es_del_equipo_contrario = p.ronda.manojo(&self.jid).jugador.equipo != p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo;
+      let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
+      let es_del_equipo_contrario = Some(p.ronda.manojo(self.jid).jugador.equipo) != p.ronda.equipo_de(p.ronda.envite.cantado_por);
       let ok = tiene_flor && es_del_equipo_contrario;
 
       if !ok {
-        if p.verbose {
-          pkts.push(enco::Packet{
-            destination: vec![self.jid.clone()],
-            message: enco::Message(
-              enco::Content::Error {
-                msg: "La jugada no es valida".to_string(),
-              }
-            )
-          });
-        }
-        return (pkts, false);
+        return Err(Error::JugadaIlegal("La jugada no es valida".to_string()));
       }
     }
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -1655,9 +1498,9 @@ impl IJugada for ResponderNoQuiero {
       return pkts
     }
 
-    let el_envido_es_respondible = (p.ronda.envite.estado >= EstadoEnvite::Envido && p.ronda.envite.estado <= EstadoEnvite::FaltaEnvido) && p.ronda.envite.cantado_por != self.jid;
-    let la_flor_es_respondible = p.ronda.envite.estado >= EstadoEnvite::Flor && p.ronda.envite.cantado_por != self.jid;
-    let el_truco_es_respondible = p.ronda.truco.estado.es_truco_respondible() && p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
+    let el_envido_es_respondible = (p.ronda.envite.estado >= EstadoEnvite::Envido && p.ronda.envite.estado <= EstadoEnvite::FaltaEnvido) && p.ronda.envite.cantado_por != Some(self.jid);
+    let la_flor_es_respondible = p.ronda.envite.estado >= EstadoEnvite::Flor && p.ronda.envite.cantado_por != Some(self.jid);
+    let el_truco_es_respondible = p.ronda.truco.estado.es_truco_respondible() && p.ronda.equipo_de(p.ronda.truco.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
 
     if el_envido_es_respondible {
       if p.verbose {
@@ -1665,7 +1508,7 @@ impl IJugada for ResponderNoQuiero {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::NoQuiero {
-              autor: self.jid.clone(),
+              autor: p.ronda.nombre(self.jid).to_string(),
             }
           )
         });
@@ -1689,7 +1532,7 @@ impl IJugada for ResponderNoQuiero {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::SumaPts {
-              autor: p.ronda.envite.cantado_por.clone(),
+              autor: nombre(p, p.ronda.envite.cantado_por),
               razon: enco::Razon::EnviteNoQuerido,
               pts: total_pts
             }
@@ -1697,10 +1540,9 @@ impl IJugada for ResponderNoQuiero {
         });
       }
 
-      p.suma_puntos(
-        p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo, 
-        total_pts
-      );
+      if let Some(e) = p.ronda.equipo_de(p.ronda.envite.cantado_por) {
+        p.suma_puntos(e, total_pts);
+      }
 
     } else if la_flor_es_respondible {
 
@@ -1709,7 +1551,7 @@ impl IJugada for ResponderNoQuiero {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::ConFlorMeAchico {
-              autor: self.jid.clone(),
+              autor: p.ronda.nombre(self.jid).to_string(),
             }
           )
         });
@@ -1719,8 +1561,8 @@ impl IJugada for ResponderNoQuiero {
       let mut total_pts = 0;
 
       for m in p.ronda.manojos.iter() {
-        let es_del_equipo_contrario = p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
-        let (tiene_flor, _) = m.tiene_flor(&p.ronda.muestra);
+        let es_del_equipo_contrario = p.ronda.equipo_de(p.ronda.envite.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
+        let (tiene_flor, _) = p.ronda.tiene_flor(m);
         if tiene_flor && es_del_equipo_contrario {
           total_pts += 3
         }
@@ -1739,17 +1581,16 @@ impl IJugada for ResponderNoQuiero {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::SumaPts { 
-              autor: p.ronda.envite.cantado_por.clone(),
+              autor: nombre(p, p.ronda.envite.cantado_por),
               razon: enco::Razon::FlorAchicada,
               pts: total_pts
             }
           )
         });
       }
-      p.suma_puntos(
-        p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo, 
-        total_pts
-      );
+      if let Some(e) = p.ronda.equipo_de(p.ronda.envite.cantado_por) {
+        p.suma_puntos(e, total_pts);
+      }
 
     } else if el_truco_es_respondible {
       if p.verbose {
@@ -1757,7 +1598,7 @@ impl IJugada for ResponderNoQuiero {
           destination: vec!["ALL".to_string()],
           message: enco::Message(
             enco::Content::NoQuiero { 
-              autor: self.jid.clone(),
+              autor: p.ronda.nombre(self.jid).to_string(),
             }
           )
         });
@@ -1765,21 +1606,23 @@ impl IJugada for ResponderNoQuiero {
 
       // pongo al equipo que propuso el truco como ganador de la mano actual
       let mano_actual = p.ronda.mano_en_juego as usize;
-      p.ronda.manos[mano_actual].ganador = p.ronda.truco.cantado_por.clone();
+      p.ronda.manos[mano_actual].ganador = p.ronda.truco.cantado_por;
       let mut equipo_ganador = Resultado::GanoAzul;
-      if p.ronda.manojo(&p.ronda.truco.cantado_por).jugador.equipo == Equipo::Rojo {
+      if p.ronda.equipo_de(p.ronda.truco.cantado_por) == Some(Equipo::Rojo) {
         equipo_ganador = Resultado::GanoRojo;
       }
       p.ronda.manos[mano_actual].resultado = equipo_ganador;
 
       let (nueva_ronda, mut res) = p.evaluar_ronda();
       pkts.append(&mut res);
-      if nueva_ronda {
-        if !p.terminada() {
+      if nueva_ronda
+        && !p.terminada() {
           // ahora se deberia de incrementar el mano
           // y ser el turno de este
-          let sig_mano = p.ronda.get_sig_el_mano();
-          p.ronda.nueva_ronda(sig_mano); // todo: el tema es que cuando llama aca
+          if let Err(err) = p.nueva_ronda() {
+            pkts.append(&mut error_pkts(p, err));
+          }
+          // todo: el tema es que cuando llama aca
           // no manda mensaje de que arranco nueva ronda
           // falso: el padre que llama a .EvaluarRonda tiene que fijarse si
           // retorno true
@@ -1803,38 +1646,32 @@ impl IJugada for ResponderNoQuiero {
         // p.byeBye()
         // }
 
-      }
-
     }
 
     pkts
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct IrseAlMazo {
-  pub jid: String,
+  pub jid: usize,
 }
+
 impl IJugada for IrseAlMazo {
+  fn jugada(&self) -> Jugada {
+    Jugada::IrseAlMazo(self.clone())
+  }
   fn id(&self) -> IJugadaId {
     IJugadaId::JIdMazo
   }
-  fn ok(&self, p:&Partida) -> (Vec<enco::Packet>, bool) {
-    let mut pkts: Vec<enco::Packet> = Vec::new();
-    let ya_se_fue_al_mazo = p.ronda.manojo(&self.jid).se_fue_al_mazo;
-    let ya_tiro_todas_sus_cartas = p.ronda.manojo(&self.jid).get_cant_cartas_tiradas() == 3;
+  fn accion(&self) -> String {
+    "mazo".to_string()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
+    let ya_se_fue_al_mazo = p.ronda.manojo(self.jid).se_fue_al_mazo;
+    let ya_tiro_todas_sus_cartas = p.ronda.manojo(self.jid).get_cant_cartas_tiradas() == 3;
     if ya_se_fue_al_mazo || ya_tiro_todas_sus_cartas {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible irse al mazo ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No es posible irse al mazo ahora".to_string()));
     }
 
     let se_estaba_jugando_el_envido = p.ronda.envite.estado >= EstadoEnvite::Envido && p.ronda.envite.estado <= EstadoEnvite::FaltaEnvido;
@@ -1846,15 +1683,15 @@ impl IJugada for IrseAlMazo {
     // 3. tampoco se puede ir al mazo si el grito el truco
 
     // envidoPropuesto = Contains([]EstadoEnvite{EstadoEnvite::Envido, EstadoEnvite::RealEnvido, EstadoEnvite::FaltaEnvido}, p.ronda.envite.estado);
-    // envidoPropuestoPorSuEquipo = p.ronda.manojo[p.ronda.envite.cantado_por].jugador.equipo == p.ronda.manojo(&self.jid).jugador.equipo;
+    // envidoPropuestoPorSuEquipo = p.ronda.manojo[p.ronda.envite.cantado_por].jugador.equipo == p.ronda.manojo(self.jid).jugador.equipo;
     // trucoPropuesto = p.ronda.truco.estado.es_truco_respondible();
-    // trucoPropuestoPorSuEquipo = p.ronda.manojo[p.ronda.truco.cantado_por].jugador.equipo == p.ronda.manojo(&self.jid).jugador.equipo;
+    // trucoPropuestoPorSuEquipo = p.ronda.manojo[p.ronda.truco.cantado_por].jugador.equipo == p.ronda.manojo(self.jid).jugador.equipo;
     // condicionDelBobo = (envidoPropuesto && envidoPropuestoPorSuEquipo) || (trucoPropuesto && trucoPropuestoPorSuEquipo);
 
     // if condicionDelBobo {
 
     // enco.Write(p.Stdout, enco.Pkt(
-    // 	enco.Dest(p.ronda.manojo(&self.jid).jugador.id),
+    // 	enco.Dest(p.ronda.manojo(self.jid).jugador.id),
     // 	enco.Msg(enco.Error,  fmt.Sprintf("No es posible irse al mazo ahora porque hay propuestas de tu equipo sin responder")),
     // ))
 
@@ -1862,21 +1699,11 @@ impl IJugada for IrseAlMazo {
 
     // }
 
-    let no_se_puede_ir_por_el_envite = (se_estaba_jugando_el_envido || se_estaba_jugando_la_flor) && p.ronda.envite.cantado_por == self.jid;
+    let no_se_puede_ir_por_el_envite = (se_estaba_jugando_el_envido || se_estaba_jugando_la_flor) && p.ronda.envite.cantado_por == Some(self.jid);
     // la de la flor es igual al del envido; porque es un envite
-    let no_se_puede_ir_por_el_truco = se_estaba_jugando_el_truco && p.ronda.truco.cantado_por == self.jid;
+    let no_se_puede_ir_por_el_truco = se_estaba_jugando_el_truco && p.ronda.truco.cantado_por == Some(self.jid);
     if no_se_puede_ir_por_el_envite || no_se_puede_ir_por_el_truco {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible irse al mazo ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No es posible irse al mazo ahora".to_string()));
     }
 
     // por como esta hecho el algoritmo EvaluarMano:
@@ -1886,30 +1713,20 @@ impl IJugada for IrseAlMazo {
     let mano_en_juego = p.ronda.mano_en_juego as usize;
     let tiradas = &p.ronda.manos[mano_en_juego].cartas_tiradas;
     let n = tiradas.len();
-    let solo_mi_equipo_tiro = n == 1 && p.ronda.manojo(&tiradas[n-1].jugador).jugador.equipo == p.ronda.manojo(&self.jid).jugador.equipo;
-    let equipo_del_jugador = p.ronda.manojo(&self.jid).jugador.equipo;
+    let solo_mi_equipo_tiro = n == 1 && p.ronda.manojo(tiradas[n-1].jugador).jugador.equipo == p.ronda.manojo(self.jid).jugador.equipo;
+    let equipo_del_jugador = p.ronda.manojo(self.jid).jugador.equipo;
     let soy_el_unico_de_mi_equipo = p.ronda.cant_jugadores_en_juego[&equipo_del_jugador] == 1;
     let no_se_puede_ir = es_primera_mano && solo_mi_equipo_tiro && soy_el_unico_de_mi_equipo;
 
     // que pasa si alguien dice truco y se va al mazo?
 
     if no_se_puede_ir {
-      if p.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![self.jid.clone()],
-          message: enco::Message(
-            enco::Content::Error {
-              msg: "No es posible irse al mazo ahora".to_string(),
-            }
-          )
-        });
-      }
-      return (pkts, false);
+      return Err(Error::JugadaIlegal("No es posible irse al mazo ahora".to_string()));
     }
-    (pkts, true)
+    Ok(())
   }
 
-  fn hacer(&self, p:&mut Partida) -> Vec<enco::Packet> {
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
     let (mut pre, ok) = self.ok(p);
     pkts.append(&mut pre);
@@ -1924,33 +1741,32 @@ impl IJugada for IrseAlMazo {
         destination: vec!["ALL".to_string()],
         message: enco::Message(
           enco::Content::Mazo {
-            autor: self.jid.clone(),
+            autor: p.ronda.nombre(self.jid).to_string(),
           }
         )
       });
     }
 
-    p.ir_al_mazo(&self.jid);
+    p.ir_al_mazo(self.jid);
 
-    let equipo_del_jugador = p.ronda.manojo(&self.jid).jugador.equipo;
+    let equipo_del_jugador = p.ronda.manojo(self.jid).jugador.equipo;
 
     let se_fueron_todos = p.ronda.cant_jugadores_en_juego[&equipo_del_jugador] == 0;
 
     // si tenia flor -> ya no lo tomo en cuenta
-    let (tiene_flor, _) = p.ronda.manojo(&self.jid).tiene_flor(&p.ronda.muestra);
+    let (tiene_flor, _) = p.ronda.tiene_flor(p.ronda.manojo(self.jid));
     if tiene_flor {
-      p.ronda.envite.jugadores_con_flor
-        .remove(
-          p.ronda.envite.jugadores_con_flor
-                  .iter()
-                  .position(|x| *x == self.jid)
-                  .expect("needle not found")
-        );
+      if let Some(pos) = p.ronda.envite.jugadores_con_flor
+        .iter()
+        .position(|x| *x == self.jid)
+      {
+        p.ronda.envite.jugadores_con_flor.remove(pos);
+      }
 
-      p.ronda.envite.canto_flor(&self.jid);
+      p.ronda.envite.canto_flor(self.jid);
       // que pasa si era el ultimo que se esperaba que cantara flor?
       // tengo que Hacer el Eval de la flor
-      let todos_los_jugadores_con_flor_cantaron = p.ronda.envite.sin_cantar.len() == 0;
+      let todos_los_jugadores_con_flor_cantaron = p.ronda.envite.sin_cantar.is_empty();
       if todos_los_jugadores_con_flor_cantaron {
         let mut res = eval_flor(p);
         pkts.append(&mut res);
@@ -1959,7 +1775,7 @@ impl IJugada for IrseAlMazo {
 
     // era el ultimo en tirar de esta mano?
     let era_el_ultimo_en_tirar = 
-      p.ronda.get_siguiente_habilitado(p.ronda.manojo(&self.jid)).is_none();
+      p.ronda.get_sig_habilitado(self.jid).is_none();
 
     if se_fueron_todos {
 
@@ -1992,7 +1808,7 @@ impl IJugada for IrseAlMazo {
             destination: vec!["ALL".to_string()],
             message: enco::Message(
               enco::Content::SumaPts {
-                autor: p.ronda.envite.cantado_por.clone(),
+                autor: nombre(p, p.ronda.envite.cantado_por),
                 razon: enco::Razon::EnviteNoQuerido,
                 pts: total_pts
               }
@@ -2000,10 +1816,9 @@ impl IJugada for IrseAlMazo {
           });
         }
 
-        p.suma_puntos(
-          p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo, 
-          total_pts
-        );
+        if let Some(e) = p.ronda.equipo_de(p.ronda.envite.cantado_por) {
+          p.suma_puntos(e, total_pts);
+        }
 
       }
 
@@ -2019,8 +1834,8 @@ impl IJugada for IrseAlMazo {
         let mut total_pts = 0;
 
         for m in p.ronda.manojos.iter() {
-          let es_del_equipo_contrario = p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo != p.ronda.manojo(&self.jid).jugador.equipo;
-          let (tiene_flor, _) = m.tiene_flor(&p.ronda.muestra);
+          let es_del_equipo_contrario = p.ronda.equipo_de(p.ronda.envite.cantado_por) != Some(p.ronda.manojo(self.jid).jugador.equipo);
+          let (tiene_flor, _) = p.ronda.tiene_flor(m);
           if tiene_flor && es_del_equipo_contrario {
             total_pts += 3
           }
@@ -2040,7 +1855,7 @@ impl IJugada for IrseAlMazo {
             destination: vec!["ALL".to_string()],
             message: enco::Message(
               enco::Content::SumaPts {
-                autor: p.ronda.envite.cantado_por.clone(),
+                autor: nombre(p, p.ronda.envite.cantado_por),
                 razon: enco::Razon::FlorAchicada,
                 pts: total_pts
               }
@@ -2048,10 +1863,9 @@ impl IJugada for IrseAlMazo {
           });
         }
     
-        p.suma_puntos(
-          p.ronda.manojo(&p.ronda.envite.cantado_por).jugador.equipo, 
-          total_pts
-        );
+        if let Some(e) = p.ronda.equipo_de(p.ronda.envite.cantado_por) {
+          p.suma_puntos(e, total_pts);
+        }
 
       }
     }
@@ -2074,7 +1888,9 @@ impl IJugada for IrseAlMazo {
 
         // actualizo el mano
         p.ronda.mano_en_juego.inc();
-        p.ronda.set_next_turno_pos_mano();
+        if let Err(err) = p.ronda.set_next_turno_pos_mano() {
+          pkts.append(&mut error_pkts(p, err));
+        }
         // lo envio
         if p.verbose {
           pkts.push(enco::Packet{
@@ -2092,8 +1908,10 @@ impl IJugada for IrseAlMazo {
         if !p.terminada() {
           // ahora se deberia de incrementar el mano
           // y ser el turno de este
-          let sig_mano = p.ronda.get_sig_el_mano();
-          p.ronda.nueva_ronda(sig_mano); // todo: el tema es que cuando llama aca
+          if let Err(err) = p.nueva_ronda() {
+            pkts.append(&mut error_pkts(p, err));
+          }
+          // todo: el tema es que cuando llama aca
           // no manda mensaje de que arranco nueva ronda
           // falso: el padre que llama a .EvaluarRonda tiene que fijarse si
           // retorno true
@@ -2118,9 +1936,11 @@ impl IJugada for IrseAlMazo {
       }
     } else {
       // cambio de turno solo si era su turno
-      let era_su_turno = p.ronda.get_el_turno().jugador.id == self.jid;
+      let era_su_turno = p.ronda.turno == self.jid;
       if era_su_turno {
-        p.ronda.set_next_turno();
+        if let Err(err) = p.ronda.set_next_turno() {
+          pkts.append(&mut error_pkts(p, err));
+        }
         if p.verbose {
           pkts.push(enco::Packet{
             destination: vec!["ALL".to_string()],
@@ -2138,3 +1958,98 @@ impl IJugada for IrseAlMazo {
   }
 }
 
+// arma el enum `Jugada` con una variante por cada tipo de jugada (con el
+// mismo nombre) y las conversiones entre ellos; todos tienen un `jid`
+macro_rules! jugadas {
+  ($($t:ident),* $(,)?) => {
+    // todas las jugadas posibles como un valor cerrado: a diferencia de
+    // `Box<dyn IJugada>` se puede comparar, hashear y serializar
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+    pub enum Jugada {
+      $($t($t),)*
+    }
+
+    impl Jugada {
+      fn como_ijugada(&self) -> &dyn IJugada {
+        match self {
+          $(Jugada::$t(j) => j,)*
+        }
+      }
+
+      pub fn jid(&self) -> usize {
+        match self {
+          $(Jugada::$t(j) => j.jid,)*
+        }
+      }
+
+      // la misma jugada pero hecha por el jugador de indice `jid`
+      pub fn con_jid(&self, jid: usize) -> Jugada {
+        let mut j = self.clone();
+        match &mut j {
+          $(Jugada::$t(j) => j.jid = jid,)*
+        }
+        j
+      }
+    }
+
+    impl From<Jugada> for Box<dyn IJugada> {
+      fn from(j: Jugada) -> Box<dyn IJugada> {
+        match j {
+          $(Jugada::$t(j) => Box::new(j),)*
+        }
+      }
+    }
+
+    $(
+      impl From<$t> for Jugada {
+        fn from(j: $t) -> Jugada {
+          Jugada::$t(j)
+        }
+      }
+
+      impl TryFrom<Jugada> for $t {
+        type Error = Jugada;
+        fn try_from(j: Jugada) -> Result<$t, Jugada> {
+          match j {
+            Jugada::$t(j) => Ok(j),
+            otra => Err(otra),
+          }
+        }
+      }
+    )*
+  };
+}
+
+jugadas!(
+  TirarCarta,
+  TocarEnvido,
+  TocarRealEnvido,
+  TocarFaltaEnvido,
+  CantarFlor,
+  CantarContraFlor,
+  CantarContraFlorAlResto,
+  GritarTruco,
+  GritarReTruco,
+  GritarVale4,
+  ResponderQuiero,
+  ResponderNoQuiero,
+  IrseAlMazo,
+);
+
+impl IJugada for Jugada {
+  fn jugada(&self) -> Jugada {
+    self.clone()
+  }
+  fn id(&self) -> IJugadaId {
+    self.como_ijugada().id()
+  }
+  fn accion(&self) -> String {
+    self.como_ijugada().accion()
+  }
+  fn validar_en_ronda(&self, p:&Partida) -> Result<(), Error> {
+    self.como_ijugada().validar_en_ronda(p)
+  }
+  fn hacer_en_ronda(&self, p:&mut Partida) -> Vec<enco::Packet> {
+    self.como_ijugada().hacer_en_ronda(p)
+  }
+}
@@ -1,7 +1,9 @@
 // imports
+mod error;
 mod equipo;
 mod jugador;
 mod carta;
+mod tablas;
 mod manojo;
 mod mano;
 mod truco;
@@ -9,16 +11,28 @@ mod envite;
 mod ronda;
 mod partida;
 mod jugada;
+mod reglas;
+mod historial;
+mod pica_pica;
+mod snapshot;
+mod observacion;
+mod entorno;
+mod sim;
+mod doble_dummy;
+mod analisis;
 pub mod enco;
 pub mod chi;
+pub mod bot;
 
 // `use` ~ import without namespace
 // `pub` ~ export
 
 // declaralos aca como pub hace que sea posible usarlos en /test
+pub use self::error::{*};
 pub use self::equipo::{*};
 pub use self::jugador::{*};
 pub use self::carta::{*};
+pub use self::tablas::{inicializar_tablas};
 pub use self::manojo::{*};
 pub use self::mano::{*};
 pub use self::truco::{*};
@@ -26,5 +40,15 @@ pub use self::envite::{*};
 pub use self::ronda::{*};
 pub use self::partida::{*};
 pub use self::jugada::{*};
+pub use self::reglas::{*};
+pub use self::historial::{*};
+pub use self::pica_pica::{*};
+pub use self::snapshot::{*};
+pub use self::observacion::{*};
+pub use self::entorno::{*};
+pub use self::sim::{*};
+pub use self::doble_dummy::{*};
+pub use self::analisis::{*};
 pub use self::enco::{*};
-pub use self::chi::{*};
\ No newline at end of file
+pub use self::chi::{*};
+pub use self::bot::{*};
\ No newline at end of file
@@ -24,7 +24,7 @@ impl NumMano {
   }
 }
 
-#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Copy, Clone)]
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Copy, Clone, Hash)]
 #[serde(rename_all = "camelCase")]
 pub enum Resultado {
   Indeterminado,
@@ -36,7 +36,8 @@ pub enum Resultado {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CartaTirada {
-  pub jugador: String,
+  // indice en `Ronda.manojos`
+  pub jugador: usize,
   pub carta: Carta,
 }
 
@@ -44,7 +45,7 @@ pub struct CartaTirada {
 #[serde(rename_all = "lowercase")]
 pub struct Mano {
   pub resultado: Resultado,
-  pub ganador: String,
+  pub ganador: Option<usize>,
   pub cartas_tiradas: Vec<CartaTirada>,
 }
 
@@ -59,7 +60,7 @@ impl Default for Mano {
     Mano{
       cartas_tiradas: Vec::<CartaTirada>::new(),
       resultado: crate::Resultado::Indeterminado,
-      ganador: String::from(""),
+      ganador: None,
     }
   }
 }
@@ -1,8 +1,9 @@
-use std::cmp::max;
 use serde::{Deserialize, Serialize};
 use crate::carta::*;
 use crate::jugador::*;
 use crate::mano::*;
+use crate::error::{Error};
+use crate::tablas;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
@@ -17,23 +18,36 @@ pub struct Manojo {
 impl Manojo {
   pub fn new(jugador: Jugador, cartas: [Carta; 3]) -> Manojo {
     Manojo{
-      jugador: jugador,
+      jugador,
       se_fue_al_mazo: false,
       ultima_tirada: -1,
       tiradas: [false; 3],
-      cartas: cartas
+      cartas
     }
   }
 
-  pub fn tirar_carta(&mut self, idx:usize) -> CartaTirada {
+  // `jid` es el indice de este manojo en `Ronda.manojos`
+  pub fn tirar_carta(&mut self, idx:usize, jid:usize) -> CartaTirada {
     self.tiradas[idx] = true;
     self.ultima_tirada = idx as isize;
-    let carta = self.cartas[idx].clone();
-    let tirada = CartaTirada{
-      jugador: self.jugador.id.clone(),
-      carta: carta
-    };
-    tirada
+    let carta = self.cartas[idx];
+    CartaTirada{
+      jugador: jid,
+      carta
+    }
+  }
+
+  // clave del manojo que no depende del orden de las cartas: el producto
+  // de sus `puid`; como cada carta tiene un primo distinto, por la
+  // factorizacion unica no hay 2 conjuntos de cartas con la misma clave
+  pub fn clave(&self) -> u64 {
+    self.cartas.iter().map(|c| c.puid() as u64).product()
+  }
+
+  // como `clave` pero distinguiendo ademas la muestra (que no puede salir
+  // del producto: la muestra es una carta mas de la baraja)
+  pub fn clave_con_muestra(&self, muestra: &Carta) -> u64 {
+    self.clave() * 40 + muestra.id() as u64
   }
 
   pub fn get_cant_cartas_tiradas(&self) -> usize {
@@ -49,17 +63,28 @@ impl Manojo {
     }
   }
 
-  pub fn get_carta_idx(&self, carta: &Carta) -> usize {
-    self.cartas.iter().position(|c| c == carta).unwrap()
+  pub fn get_carta_idx(&self, carta: &Carta) -> Result<usize, Error> {
+    self.cartas
+      .iter()
+      .position(|c| c == carta)
+      .ok_or(Error::CartaNoEnMano{jid: self.jugador.id.clone(), carta: *carta})
   }
 
   pub fn tiene_flor(&self, muestra: &Carta) -> (bool,isize) {
+    match tablas::tabla_manojo(self, muestra) {
+      Some((tipo_flor, _, _)) => (tipo_flor > 0, tipo_flor),
+      None => self.tiene_flor_sin_tabla(muestra),
+    }
+  }
+
+  // igual que `tiene_flor` pero sin usar las tablas (con las que se arman)
+  pub fn tiene_flor_sin_tabla(&self, muestra: &Carta) -> (bool,isize) {
     // caso 1: al menos dos piezas
     let mut num_piezas = 0;
     let mut pieza_ix = 0;
 
     for (ix, c) in self.cartas.iter().enumerate() {
-        if c.es_pieza(muestra) {
+        if c.es_pieza_sin_tabla(muestra) {
           num_piezas += 1;
           pieza_ix = ix;
         }
@@ -91,12 +116,18 @@ impl Manojo {
       return (true, 3)
     }
 
-    return (false, -1);
+    (false, -1)
   }
 
   pub fn calc_flor(&self, muestra: &Carta) -> isize {
-    let puntaje_flor: usize ;
-    let (tiene_flor, tipo_flor) = self.tiene_flor(muestra);
+    match tablas::tabla_manojo(self, muestra) {
+      Some((_, flor, _)) => flor,
+      None => self.calc_flor_sin_tabla(muestra),
+    }
+  }
+
+  pub fn calc_flor_sin_tabla(&self, muestra: &Carta) -> isize {
+    let (tiene_flor, tipo_flor) = self.tiene_flor_sin_tabla(muestra);
     if !tiene_flor {
       return -1
     }
@@ -104,10 +135,10 @@ impl Manojo {
     let mut ptjs =
       self.cartas
         .iter()
-        .map(|c| c.calc_puntaje(muestra))
+        .map(|c| c.calc_puntaje_sin_tabla(muestra))
         .collect::<Vec<usize>>();
     
-    if tipo_flor == 1 {
+    let puntaje_flor = if tipo_flor == 1 {
       let (max_ix, max) = ptjs
         .iter()
         .enumerate()
@@ -125,12 +156,14 @@ impl Manojo {
       //     .unwrap();
 
         ptjs.remove(max_ix);
-        puntaje_flor = max + ptjs.iter().map(|p|p % 10).sum::<usize>();
+        max + ptjs.iter().map(|p|p % 10).sum::<usize>()
     } else {
-      puntaje_flor = ptjs.iter().sum::<usize>();
-    }
+      // sin piezas (3 del mismo palo) se suman 20
+      let hay_pieza = self.cartas.iter().any(|c| c.es_pieza_sin_tabla(muestra));
+      ptjs.iter().sum::<usize>() + if hay_pieza {0} else {20}
+    };
 
-    return puntaje_flor as isize;
+    puntaje_flor as isize
   }
 
   // tiene2DelMismoPalo devuelve `true` si tiene dos cartas
@@ -144,31 +177,42 @@ impl Manojo {
         }
       }
     }
-    return (false, (0, 0))
+    (false, (0, 0))
   }
 
   // CalcularEnvido devuelve el puntaje correspondiente al envido del manojo
   // PRE: no tiene flor
   pub fn calcular_envido(&self, muestra: &Carta) -> usize {
-    let (tiene_2_del_mismo_palo, ixs) = self.tiene_2_del_mismo_palo();
-    if tiene_2_del_mismo_palo {
-      let (x,y) = (
-        self.cartas[ixs.0].calc_puntaje(&muestra),
-        self.cartas[ixs.1].calc_puntaje(&muestra)
-      );
-      let no_tiene_niguna_pieza = max(x,y) < 27;
-      return if no_tiene_niguna_pieza {x + y + 20} else {x + y};
-    } else {
-      // si no, entonces implemente suma las 2 de mayor valor
-      let mut pts: Vec<usize> = 
-        self.cartas
-          .iter()
-          .map(|c| c.calc_puntaje(muestra))
-          .collect::<Vec<usize>>();
-      pts.sort();
-      pts.reverse();
-      return pts.iter().take(2).sum();
+    match tablas::tabla_manojo(self, muestra) {
+      Some((_, _, envido)) => envido,
+      None => self.calcular_envido_sin_tabla(muestra),
     }
   }
+
+  pub fn calcular_envido_sin_tabla(&self, muestra: &Carta) -> usize {
+    let pts = self.cartas.map(|c| c.calc_puntaje_sin_tabla(muestra));
+    // con pieza: la pieza mas la mas alta de las otras dos
+    let pieza = (0..3)
+      .filter(|ix| self.cartas[*ix].es_pieza_sin_tabla(muestra))
+      .max_by_key(|ix| pts[*ix]);
+    if let Some(pix) = pieza {
+      let resto = (0..3).filter(|ix| *ix != pix).map(|ix| pts[ix]).max().unwrap();
+      return pts[pix] + resto;
+    }
+    // sin pieza: 20 mas las 2 del mismo palo (las mejores, si son 3)
+    let mismo_palo = [(0, 1), (0, 2), (1, 2)]
+      .into_iter()
+      .filter(|(i, j)| self.cartas[*i].palo == self.cartas[*j].palo)
+      .map(|(i, j)| pts[i] + pts[j])
+      .max();
+    if let Some(pts) = mismo_palo {
+      return pts + 20;
+    }
+    // si no, entonces simplemente suma las 2 de mayor valor
+    let mut pts = pts;
+    pts.sort();
+    pts[1] + pts[2]
+  }
+
   
 }
\ No newline at end of file
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::carta::{Carta};
+use crate::envite::{EstadoEnvite};
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::jugador::{Jugador};
+use crate::mano::{Mano, NumMano};
+use crate::partida::{Partida};
+use crate::truco::{Truco};
+
+// un manojo tal como lo ve otro jugador: `None` es una carta que no ve
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ManojoObservado {
+  pub jugador: Jugador,
+  pub cartas: [Option<Carta>; 3],
+  pub tiradas: [bool; 3],
+  pub se_fue_al_mazo: bool,
+}
+
+// la parte publica del envite: quien canto que y por cuanto
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EnviteObservado {
+  pub estado: EstadoEnvite,
+  pub puntaje: usize,
+  // indice del jugador en `manojos`
+  pub cantado_por: Option<usize>,
+}
+
+// todo lo que el jugador `jid` puede saber de la partida: su mano, la
+// muestra, lo que hay en la mesa, lo cantado y los puntajes
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Observacion {
+  pub jid: String,
+  pub puntuacion: usize,
+  pub puntajes: HashMap<Equipo, usize>,
+  pub mano_en_juego: NumMano,
+  pub el_mano: usize,
+  pub turno: usize,
+  pub envite: EnviteObservado,
+  // true si `jid` tiene flor y todavia no la canto
+  pub flor_pendiente: bool,
+  pub truco: Truco,
+  pub muestra: Carta,
+  pub manojos: Vec<ManojoObservado>,
+  pub manos: [Mano; 3],
+  // en una ronda de pica-pica, el duelo de `jid` (si no termino)
+  pub duelo: Option<Box<Observacion>>,
+}
+
+impl Partida {
+  pub fn observar(&self, jid: &str) -> Result<Observacion, Error> {
+    // checkeo que exista
+    let mix = self.ronda.get_mix(jid)?;
+    let r = &self.ronda;
+
+    let manojos = r.manojos
+      .iter()
+      .map(|m| {
+        let es_el = m.jugador.id == jid;
+        let mut cartas = [None; 3];
+        for (ix, c) in m.cartas.iter().enumerate() {
+          if es_el || m.tiradas[ix] {
+            cartas[ix] = Some(*c);
+          }
+        }
+        ManojoObservado {
+          jugador: m.jugador.clone(),
+          cartas,
+          tiradas: m.tiradas,
+          se_fue_al_mazo: m.se_fue_al_mazo,
+        }
+      })
+      .collect::<Vec<ManojoObservado>>();
+
+    let duelo = match self.get_duelo(jid) {
+      Some(d) => Some(Box::new(d.observar(jid)?)),
+      None => None,
+    };
+
+    Ok(Observacion {
+      jid: jid.to_string(),
+      puntuacion: self.puntuacion,
+      puntajes: self.puntajes.clone(),
+      mano_en_juego: r.mano_en_juego,
+      el_mano: r.el_mano,
+      turno: r.turno,
+      envite: EnviteObservado {
+        estado: r.envite.estado,
+        puntaje: r.envite.puntaje,
+        cantado_por: r.envite.cantado_por,
+      },
+      flor_pendiente: r.envite.sin_cantar.contains(&mix),
+      truco: r.truco.clone(),
+      muestra: r.muestra,
+      manojos,
+      manos: r.manos.clone(),
+      duelo,
+    })
+  }
+}
@@ -1,22 +1,40 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use crate::equipo::{Equipo};
 use crate::{EstadoEnvite, EstadoTruco, Palo, Carta, TirarCarta};
 use crate::ronda::{Ronda};
+use crate::historial::{Historial, Reparto};
+use crate::reglas::{Reglas};
+use crate::pica_pica::{Duelo};
 use crate::enco;
+use crate::error::{Error};
 use crate::mano::{NumMano, Resultado};
 use crate::{IJugada, IrseAlMazo, TocarEnvido, TocarRealEnvido, TocarFaltaEnvido, 
   CantarFlor, CantarContraFlor, CantarContraFlorAlResto, GritarTruco, 
   GritarReTruco, GritarVale4, ResponderQuiero, ResponderNoQuiero };
 use regex::Regex;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Partida{
   pub puntuacion: usize,
   pub puntajes: HashMap<Equipo, usize>,
   pub ronda: Ronda,
+  // los mano-a-mano de una ronda de pica-pica (vacio si no lo es)
+  #[serde(default)]
+  pub duelos: Vec<Duelo>,
   #[serde(skip_deserializing, skip_serializing)]
   pub verbose: bool,
+  // solo registra las jugadas aplicadas con `jugar` o `cmd`; las que se
+  // aplican directo con `IJugada::hacer` (ej. `Entorno`) no quedan
+  #[serde(skip_deserializing, skip_serializing)]
+  pub historial: Historial,
+  // las jugadas deshechas que se pueden rehacer, la proxima al final (ver
+  // `deshacer`); compartidas entre copias para que clonar no las copie
+  #[serde(skip_deserializing, skip_serializing)]
+  rehechas: Arc<Vec<String>>,
 }
 
 impl Partida {
@@ -25,10 +43,89 @@ impl Partida {
     azules: Vec<String>,
     rojos: Vec<String>,
     verbose: bool,
-  ) -> Result<Partida, &'static str> {
+  ) -> Result<Partida, Error> {
+    Partida::new_con_reglas(puntuacion, azules, rojos, Reglas::default(), verbose)
+  }
+
+  pub fn new_con_reglas(
+    puntuacion:usize,
+    azules: Vec<String>,
+    rojos: Vec<String>,
+    reglas: Reglas,
+    verbose: bool,
+  ) -> Result<Partida, Error> {
+    Partida::new_with_reglas_y_rng(puntuacion, azules, rojos, reglas, verbose, None)
+  }
+
+  // crea una partida cuyos repartos quedan determinados por `seed`:
+  // dos partidas con la misma semilla y las mismas jugadas son identicas.
+  // El generador no viaja en el json; para reproducir una partida guardada
+  // usar su `historial` (ver `reproducir`)
+  pub fn new_with_seed(
+    puntuacion:usize,
+    azules: Vec<String>,
+    rojos: Vec<String>,
+    verbose: bool,
+    seed: u64,
+  ) -> Result<Partida, Error> {
+    let rng = StdRng::seed_from_u64(seed);
+    Partida::new_with_rng(puntuacion, azules, rojos, verbose, Some(rng))
+  }
+
+  pub fn new_with_rng(
+    puntuacion:usize,
+    azules: Vec<String>,
+    rojos: Vec<String>,
+    verbose: bool,
+    rng: Option<StdRng>,
+  ) -> Result<Partida, Error> {
+    Partida::new_with_reglas_y_rng(puntuacion, azules, rojos, Reglas::default(), verbose, rng)
+  }
+
+  pub fn new_with_reglas_y_rng(
+    puntuacion:usize,
+    azules: Vec<String>,
+    rojos: Vec<String>,
+    reglas: Reglas,
+    verbose: bool,
+    rng: Option<StdRng>,
+  ) -> Result<Partida, Error> {
+    Partida::check_config(puntuacion, &azules, &rojos, &reglas)?;
+    let ronda = Ronda::new_with_rng(azules.clone(), rojos.clone(), rng)?;
+    Ok(Partida::con_ronda(puntuacion, azules, rojos, reglas, verbose, ronda))
+  }
 
-    if ![20,30,40].contains(&puntuacion) {
-      return Err("la puntuacion de la partida no es valida");
+  // como `new_with_reglas_y_rng` pero con las cartas de `reparto` en vez
+  // de repartir
+  pub(crate) fn new_con_reparto(
+    puntuacion:usize,
+    azules: Vec<String>,
+    rojos: Vec<String>,
+    reglas: Reglas,
+    reparto: &Reparto,
+    verbose: bool,
+    rng: Option<StdRng>,
+  ) -> Result<Partida, Error> {
+    Partida::check_config(puntuacion, &azules, &rojos, &reglas)?;
+    let ronda = Ronda::new_con_reparto(azules.clone(), rojos.clone(), reparto, rng)?;
+    Ok(Partida::con_ronda(puntuacion, azules, rojos, reglas, verbose, ronda))
+  }
+
+  fn check_config(
+    puntuacion: usize,
+    azules: &[String],
+    rojos: &[String],
+    reglas: &Reglas,
+  ) -> Result<(), Error> {
+    if puntuacion == 0 {
+      return Err(Error::PuntuacionInvalida(puntuacion));
+    }
+
+    if let Some(el_chico) = reglas.el_chico {
+      if el_chico == 0 || el_chico >= puntuacion {
+        return Err(Error::ReglasInvalidas(
+          format!("el chico ({}) tiene que estar entre 1 y {}", el_chico, puntuacion - 1)));
+      }
     }
 
     let misma_cant_de_jugadores = azules.len() == rojos.len();
@@ -36,15 +133,90 @@ impl Partida {
     let cant_correcta = [2,4,6].contains(&cant_jugadores);
     let ok = misma_cant_de_jugadores && cant_correcta;
     if !ok {
-      return Err("la cantidad de jguadores no es correcta");
+      return Err(Error::CantidadDeJugadoresInvalida);
     }
+    Ok(())
+  }
+
+  fn con_ronda(
+    puntuacion: usize,
+    azules: Vec<String>,
+    rojos: Vec<String>,
+    reglas: Reglas,
+    verbose: bool,
+    ronda: Ronda,
+  ) -> Partida {
+    let historial = Historial {
+      puntuacion,
+      azules,
+      rojos,
+      reglas,
+      verbose,
+      repartos: Vec::new(),
+      jugadas: Vec::new(),
+    };
 
-    Ok(Partida{
-      puntuacion: puntuacion,
+    let mut p = Partida{
+      puntuacion,
       puntajes: HashMap::from([(Equipo::Azul, 0), (Equipo::Rojo, 0)]),
-      verbose: verbose,
-      ronda: Ronda::new(azules, rojos).unwrap()
-    })
+      verbose,
+      ronda,
+      duelos: Vec::new(),
+      historial,
+      rehechas: Arc::default(),
+    };
+    p.ronda.reglas = reglas;
+    p.ronda.cachear_flores(true);
+    p.historial.repartos.push(p.ronda.get_reparto());
+    p
+  }
+
+  // reconstruye la partida registrada en `historial` aplicando sus
+  // repartos y jugadas; retorna tambien todos los paquetes emitidos
+  pub fn reproducir(
+    historial: &Historial
+  ) -> Result<(Partida, Vec<enco::Packet>), Error> {
+    let mut p = Partida::desde_historial(historial)?;
+    let pkts = p.aplicar(&historial.jugadas)?;
+    if !p.ronda.repartos_pendientes.is_empty() {
+      return Err(Error::EstadoInvalido(
+        "el historial tiene repartos de mas".to_string()));
+    }
+
+    Ok((p, pkts))
+  }
+
+  // aplica las `jugadas` (en formato `cmd`) una tras otra
+  fn aplicar(&mut self, jugadas: &[String]) -> Result<Vec<enco::Packet>, Error> {
+    let mut pkts: Vec<enco::Packet> = Vec::new();
+    for jugada in jugadas.iter() {
+      pkts.append(&mut self.cmd(jugada)?);
+      if let Some(err) = self.ronda.reparto_rechazado.take() {
+        return Err(err);
+      }
+    }
+    Ok(pkts)
+  }
+
+  // la partida registrada en `historial` antes de su primera jugada, con
+  // los repartos de las rondas siguientes pendientes
+  pub fn desde_historial(historial: &Historial) -> Result<Partida, Error> {
+    let mut p = Partida::new_con_reglas(
+      historial.puntuacion,
+      historial.azules.clone(),
+      historial.rojos.clone(),
+      historial.reglas,
+      historial.verbose,
+    )?;
+
+    let (primero, resto) = historial.repartos
+      .split_first()
+      .ok_or(Error::EstadoInvalido("el historial no tiene repartos".to_string()))?;
+    p.ronda.set_reparto(primero)?;
+    p.ronda.cachear_flores(true);
+    p.ronda.repartos_pendientes = resto.iter().cloned().collect();
+    p.historial.repartos = vec![primero.clone()];
+    Ok(p)
   }
 
   /* GETTERs */
@@ -63,7 +235,7 @@ impl Partida {
   }
 
   pub fn get_puntuacion_malas(&self) -> usize{
-    self.puntuacion / 2
+    self.el_chico()
   }
 
   pub fn es_mano_a_mano(&self) -> bool{
@@ -75,7 +247,7 @@ impl Partida {
   }
 
   pub fn el_chico(&self) -> usize{
-    self.puntuacion / 2
+    self.ronda.reglas.el_chico.unwrap_or(self.puntuacion / 2)
   }
 
   pub fn esta_en_malas(&self, e:Equipo) -> bool{
@@ -83,18 +255,16 @@ impl Partida {
   }
 
   pub fn calc_pts_falta(&self) -> usize{
-    self.puntuacion - self.puntajes[&self.el_que_va_ganando()]
+    self.puntuacion.saturating_sub(self.puntajes[&self.el_que_va_ganando()])
   }
 
   pub fn calc_pts_falta_envido(&self, ganador_del_envite:Equipo) -> usize{
     if self.esta_en_malas(self.el_que_va_ganando()){
       let lo_que_le_falta_al_ganador_para_ganar_el_chico = 
-        self.el_chico() - self.puntajes[&ganador_del_envite];
+        self.el_chico().saturating_sub(self.puntajes[&ganador_del_envite]);
       return lo_que_le_falta_al_ganador_para_ganar_el_chico;
     }
-    let lo_que_le_falta_al_que_va_ganando_para_ganar_el_chico = 
-      self.calc_pts_falta();
-    lo_que_le_falta_al_que_va_ganando_para_ganar_el_chico
+    self.calc_pts_falta()
   }
 
   pub fn calc_pts_contraflor_al_resto(&self, ganador_del_envite:Equipo) -> usize{
@@ -106,8 +276,7 @@ impl Partida {
     self.terminada()
   }
 
-  pub fn tocar_envido(&mut self, jid: &str) {
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
+  pub fn tocar_envido(&mut self, jid: usize) {
     // 2 opciones: o bien no se jugo aun
 	  // o bien ya estabamos en envido
     let ya_se_habia_cantado_el_envido = 
@@ -115,17 +284,16 @@ impl Partida {
     if ya_se_habia_cantado_el_envido {
       // se aumenta el puntaje del envido en +2
       self.ronda.envite.puntaje += 2;
-      self.ronda.envite.cantado_por = m.jugador.id.clone();
+      self.ronda.envite.cantado_por = Some(jid);
     } else { // no se habia jugado aun
-      self.ronda.envite.cantado_por = m.jugador.id.clone();
+      self.ronda.envite.cantado_por = Some(jid);
       self.ronda.envite.estado = EstadoEnvite::Envido;
       self.ronda.envite.puntaje = 2;
     }    
   }
 
-  pub fn tocar_real_envido(&mut self, jid: &str){
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
-    self.ronda.envite.cantado_por = m.jugador.id.clone();
+  pub fn tocar_real_envido(&mut self, jid: usize){
+    self.ronda.envite.cantado_por = Some(jid);
     // 2 opciones:
     // o bien el envido no se jugo aun,
     // o bien ya estabamos en envido
@@ -139,20 +307,18 @@ impl Partida {
     self.ronda.envite.estado = EstadoEnvite::RealEnvido;
   }
 
-  pub fn tocar_falta_envido(&mut self, jid: &str){
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
+  pub fn tocar_falta_envido(&mut self, jid: usize){
     self.ronda.envite.estado = EstadoEnvite::FaltaEnvido;
-    self.ronda.envite.cantado_por = m.jugador.id.clone();
+    self.ronda.envite.cantado_por = Some(jid);
   }
 
-  pub fn cantar_flor(&mut self, jid: &str){
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
+  pub fn cantar_flor(&mut self, jid: usize){
     let ya_estabamos_en_flor = self.ronda.envite.estado >= EstadoEnvite::Flor;
     if ya_estabamos_en_flor{
       self.ronda.envite.puntaje += 3;
       // si estabamos en algo mas grande que `FLOR` -> no lo aumenta
       if self.ronda.envite.estado == EstadoEnvite::Flor {
-        self.ronda.envite.cantado_por = m.jugador.id.clone();
+        self.ronda.envite.cantado_por = Some(jid);
         self.ronda.envite.estado = EstadoEnvite::Flor;
       }
     } else {
@@ -160,73 +326,63 @@ impl Partida {
       // al que pertenece el que la canto en un principio para
       // poder sumarle los puntos correspondientes
       self.ronda.envite.puntaje = 3;
-      self.ronda.envite.cantado_por = m.jugador.id.clone();
+      self.ronda.envite.cantado_por = Some(jid);
       self.ronda.envite.estado = EstadoEnvite::Flor;
     }
   }
 
-  pub fn cantar_contra_flor(&mut self, jid: &str){
+  pub fn cantar_contra_flor(&mut self, jid: usize){
     self.ronda.envite.estado = EstadoEnvite::ContraFlor;
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
-    self.ronda.envite.cantado_por = m.jugador.id.clone();
+    self.ronda.envite.cantado_por = Some(jid);
     // ahora la flor pasa a jugarse por 4 puntos
     self.ronda.envite.puntaje = 4;
   }
 
-  pub fn cantar_contra_flor_al_resto(&mut self, jid: &str){
+  pub fn cantar_contra_flor_al_resto(&mut self, jid: usize){
     self.ronda.envite.estado = EstadoEnvite::ContraFlorAlResto;
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
-    self.ronda.envite.cantado_por = m.jugador.id.clone();
+    self.ronda.envite.cantado_por = Some(jid);
     // ahora la flor pasa a jugarse por 4 puntos
     self.ronda.envite.puntaje = 4; // <- eso es al pedo, es independiente
   }
 
-  pub fn gritar_truco(&mut self, jid: &str){
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
-    self.ronda.truco.cantado_por = m.jugador.id.clone();
+  pub fn gritar_truco(&mut self, jid: usize){
+    self.ronda.truco.cantado_por = Some(jid);
     self.ronda.truco.estado = EstadoTruco::Truco;
   }
 
-  pub fn querer_truco(&mut self, jid: &str){
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
-    self.ronda.truco.cantado_por = m.jugador.id.clone();
+  pub fn querer_truco(&mut self, jid: usize){
+    self.ronda.truco.cantado_por = Some(jid);
     self.ronda.truco.estado = match self.ronda.truco.estado {
       EstadoTruco::Truco => {EstadoTruco::TrucoQuerido},
       EstadoTruco::ReTruco => {EstadoTruco::ReTrucoQuerido}
       EstadoTruco::Vale4 => {EstadoTruco::Vale4Querido}
-      _ => unreachable!()
+      otro => otro
     };
   }
 
-  pub fn gritar_retruco(&mut self, jid: &str){
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
-    self.ronda.truco.cantado_por = m.jugador.id.clone();
+  pub fn gritar_retruco(&mut self, jid: usize){
+    self.ronda.truco.cantado_por = Some(jid);
     self.ronda.truco.estado = EstadoTruco::ReTruco;
   }
 
-  pub fn gritar_vale4(&mut self, jid: &str){
-    let m = &self.ronda.manojos[self.ronda.mixs[jid]];
-    self.ronda.truco.cantado_por = m.jugador.id.clone();
+  pub fn gritar_vale4(&mut self, jid: usize){
+    self.ronda.truco.cantado_por = Some(jid);
     self.ronda.truco.estado = EstadoTruco::Vale4;
   }
 
-  pub fn ir_al_mazo(&mut self, jid: &str){
-    let m = &mut self.ronda.manojos[self.ronda.mixs[jid]];
+  pub fn ir_al_mazo(&mut self, jid: usize){
+    let Some(m) = self.ronda.manojos.get_mut(jid) else { return };
     m.se_fue_al_mazo = true;
     let equipo_del_jugador = m.jugador.equipo;
     self.ronda.cant_jugadores_en_juego
       .entry(equipo_del_jugador)
       .and_modify(|v| *v -= 1);
     // lo elimino de los jugadores que tenian flor (si es que tenia)
-    if self.ronda.envite.sin_cantar.contains(&m.jugador.id) {
-      // self.ronda.envite.sin_cantar.remove(m.jugador.id)
-      self.ronda.envite.sin_cantar
-        .remove(
-          self.ronda.envite.sin_cantar
-            .iter()
-            .position(|x| *x == m.jugador.id)
-            .expect("not found")
-        );
+    if let Some(pos) = self.ronda.envite.sin_cantar
+      .iter()
+      .position(|x| *x == jid)
+    {
+      self.ronda.envite.sin_cantar.remove(pos);
     }
   }
 
@@ -243,17 +399,31 @@ impl Partida {
   //   self.ronda.get_mano_actual().agregar_tirada(tirada);
   // }
 
-  pub fn tirar_carta(&mut self, jid: &str, idx:usize) {
-    let tirada = self.ronda.manojos[self.ronda.mixs[jid]].tirar_carta(idx);
+  // reparte una ronda nueva; si corresponde, de pica-pica
+  pub fn nueva_ronda(&mut self) -> Result<(), Error> {
+    let venia_de_pica_pica = self.en_pica_pica();
+    self.duelos = Vec::new();
+    let sig_mano = self.ronda.get_sig_el_mano();
+    self.ronda.nueva_ronda(sig_mano);
+    if !venia_de_pica_pica && self.toca_pica_pica() {
+      self.armar_duelos()?;
+    }
+    Ok(())
+  }
+
+  pub fn tirar_carta(&mut self, jid: usize, idx:usize) {
+    let Some(m) = self.ronda.manojos.get_mut(jid) else { return };
+    let tirada = m.tirar_carta(idx, jid);
     self.ronda.get_mano_actual().agregar_tirada(tirada);
   }
 
-  pub fn abandono(&mut self, jid:&str) -> Vec<enco::Packet> {
+  pub fn abandono(&mut self, jid:&str) -> Result<Vec<enco::Packet>, Error> {
     // encuentra al jugador
-    let manojo = self.ronda.manojo(jid);
+    let manojo = self.ronda.get_manojo(jid)?;
     // doy por ganador al equipo contrario
     let equipo_contrario = manojo.jugador.equipo.equipo_contrario();
-    let pts_faltantes = self.puntuacion - self.puntajes[&equipo_contrario];
+    let pts_faltantes = 
+      self.puntuacion.saturating_sub(self.puntajes[&equipo_contrario]);
     self.suma_puntos(equipo_contrario, pts_faltantes);
 
     let mut pkts: Vec<enco::Packet> = Vec::new();
@@ -268,7 +438,7 @@ impl Partida {
       });
     }
 
-    pkts
+    Ok(pkts)
   }
 
   pub fn evaluar_ronda(&mut self) -> (bool, Vec<enco::Packet>) {
@@ -284,7 +454,7 @@ impl Partida {
     let mano_actual = self.ronda.mano_en_juego as usize;
     let el_truco_no_tuvo_respuesta = self.ronda.truco.estado.es_truco_respondible();
     let no_fue_parda = self.ronda.manos[mano_actual].resultado != Resultado::Empardada;
-    let esta_mano_ya_tiene_ganador = no_fue_parda && self.ronda.manos[mano_actual].ganador != "";
+    let esta_mano_ya_tiene_ganador = no_fue_parda && self.ronda.manos[mano_actual].ganador.is_some();
     let el_truco_fue_no_querido = el_truco_no_tuvo_respuesta && esta_mano_ya_tiene_ganador;
     let el_truco_fue_querido = !el_truco_fue_no_querido;
 
@@ -308,8 +478,9 @@ impl Partida {
         .iter()
         .fold(cant_manos_ganadas, |mut acc, c| {
           if c.resultado != Resultado::Empardada {
-            let e = self.ronda.manojo(&c.ganador).jugador.equipo;
-            *acc.entry(e).or_insert(0) += 1;
+            if let Some(e) = self.ronda.equipo_de(c.ganador) {
+              *acc.entry(e).or_insert(0) += 1;
+            }
           }
           acc
         });
@@ -340,13 +511,13 @@ impl Partida {
     // caso particular:
     // no puedo definir quien gano si la seguna mano no tiene definido un resultado
     let no_esta_empardada = self.ronda.manos[NumMano::Segunda as usize].resultado != Resultado::Empardada;
-    let no_tiene_ganador = self.ronda.manos[NumMano::Segunda as usize].ganador == "";
+    let no_tiene_ganador = self.ronda.manos[NumMano::Segunda as usize].ganador.is_none();
     let segunda_mano_indefinida = no_esta_empardada && no_tiene_ganador;
     // tengo que diferenciar si vengo de: TirarCarta o si vengo de un no quiero:
     // si viniera de un TirarCarta -> en la mano actual (o la anterior)? la ultima carta tirada pertenece al turno actual
     let ix_mano_en_juego = self.ronda.mano_en_juego as usize;
     let n = self.ronda.manos[ix_mano_en_juego].cartas_tiradas.len();
-    let actual = self.ronda.get_el_turno().jugador.id.clone();
+    let actual = self.ronda.turno;
     let mix = self.ronda.mano_en_juego as usize;
     let ultima_carta_tirada_pertenece_al_turno_actual = n > 0 &&
       self.ronda.manos[mix].cartas_tiradas[n-1].jugador == actual;
@@ -357,7 +528,7 @@ impl Partida {
     }
 
     // hay ganador -> ya se que al final voy a retornar un true
-    let mut ganador = "".to_string();
+    let mut ganador: Option<usize> = None;
 
     if !hay_jugadores_en_ambos {
       // enonces como antes paso por evaluar mano
@@ -365,14 +536,14 @@ impl Partida {
       // al equipo que no abandono -> lo sacao de ahi
       // caso particular: la mano resulto "empardada pero uno abandono"
       if no_fue_parda && esta_mano_ya_tiene_ganador {
-        ganador = self.ronda.get_mano_actual().ganador.clone()
+        ganador = self.ronda.get_mano_actual().ganador
       } else {
         // el ganador es el primer jugador que no se haya ido al mazo del equipo
         // que sigue en pie
         let equipo_ganador = if hay_jugadores_azul {Equipo::Azul} else {Equipo::Rojo};
-        for m in &self.ronda.manojos {
+        for (ix, m) in self.ronda.manojos.iter().enumerate() {
           if (!m.se_fue_al_mazo) && m.jugador.equipo == equipo_ganador {
-            ganador = m.jugador.id.clone();
+            ganador = Some(ix);
             break
           }
         }
@@ -381,26 +552,27 @@ impl Partida {
       // agarro cualquier manojo de los rojos
       // o bien es la Primera o bien la Segunda
       ganador = 
-        match self.ronda.manojo(&self.ronda.manos[0].ganador).jugador.equipo {
-          Equipo::Rojo => { self.ronda.manos[0].ganador.clone() },
-          Equipo::Azul => { self.ronda.manos[1].ganador.clone() },
+        match self.ronda.equipo_de(self.ronda.manos[0].ganador) {
+          Some(Equipo::Rojo) => { self.ronda.manos[0].ganador },
+          _ => { self.ronda.manos[1].ganador },
         }
 
     } else if cant_manos_ganadas[&Equipo::Azul] >= 2 {
       ganador = 
-        match self.ronda.manojo(&self.ronda.manos[0].ganador).jugador.equipo {
-          Equipo::Azul => { self.ronda.manos[0].ganador.clone() },
-          Equipo::Rojo => { self.ronda.manos[1].ganador.clone() },
+        match self.ronda.equipo_de(self.ronda.manos[0].ganador) {
+          Some(Equipo::Azul) => { self.ronda.manos[0].ganador },
+          _ => { self.ronda.manos[1].ganador },
         }
     } else {
         ganador =
           match (parda_primera, parda_segunda, parda_tercera) {
-            (true, false, false) => {self.ronda.manos[NumMano::Segunda as usize].ganador.clone()},
-            (false, true, false) => {self.ronda.manos[NumMano::Primera as usize].ganador.clone()},
-            (false, false, false) => {self.ronda.manos[NumMano::Primera as usize].ganador.clone()},
-            (true, true, false) => {self.ronda.manos[NumMano::Tercera as usize].ganador.clone()},
-            // (true, true, false) => {self.ronda.get_el_mano().jugador.id.clone()},
-            (_, _, _) => unreachable!()
+            // parda la primera: define la segunda
+            (true, false, _) => {self.ronda.manos[NumMano::Segunda as usize].ganador},
+            // gano alguien la primera: si despues hubo parda, define la primera
+            (false, _, _) => {self.ronda.manos[NumMano::Primera as usize].ganador},
+            (true, true, false) => {self.ronda.manos[NumMano::Tercera as usize].ganador},
+            // las tres pardas: gana el mano
+            (true, true, true) => {Some(self.ronda.el_mano)},
           }
     }
 
@@ -411,65 +583,37 @@ impl Partida {
       EstadoTruco::Vale4Querido => 4,
     };
 
-    if hay_jugadores_en_ambos {
-      if self.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![String::from("ALL")],
-          message: enco::Message(
-            enco::Content::RondaGanada {
-              autor: ganador.clone(),
-              razon: enco::Razon::SeFueronAlMazo
-            }
-          )
-        });
-      }
+    let razon = if !hay_jugadores_en_ambos {
+      enco::Razon::SeFueronAlMazo
     } else if el_truco_no_tuvo_respuesta {
-      ganador = self.ronda.truco.cantado_por.clone();
-      let razon = match self.ronda.truco.estado {
-        EstadoTruco::Truco => enco::Razon::TrucoNoQuerido,
-        EstadoTruco::ReTruco => enco::Razon::TrucoNoQuerido,
-        EstadoTruco::Vale4 => enco::Razon::TrucoNoQuerido,
-        _ => unreachable!(),
-      };
-      if self.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![String::from("ALL")],
-          message: enco::Message(
-            enco::Content::RondaGanada {
-              autor: ganador.clone(),
-              razon: razon
-            }
-          )
-        });
-      }
+      ganador = self.ronda.truco.cantado_por;
+      enco::Razon::TrucoNoQuerido
     } else {
-      let razon = match self.ronda.truco.estado {
-        EstadoTruco::Truco => enco::Razon::TrucoQuerido,
-        EstadoTruco::ReTruco => enco::Razon::TrucoQuerido,
-        EstadoTruco::Vale4 => enco::Razon::TrucoQuerido,
-        _ => unreachable!(),
-      };
-      if self.verbose {
-        pkts.push(enco::Packet{
-          destination: vec![String::from("ALL")],
-          message: enco::Message(
-            enco::Content::RondaGanada {
-              autor: ganador.clone(),
-              razon: razon
-            }
-          )
-        });
-      }
+      enco::Razon::TrucoQuerido
+    };
+    let autor = ganador.map_or("", |g| self.ronda.nombre(g)).to_string();
+    if self.verbose {
+      pkts.push(enco::Packet{
+        destination: vec![String::from("ALL")],
+        message: enco::Message(
+          enco::Content::RondaGanada {
+            autor: autor.clone(),
+            razon
+          }
+        )
+      });
     }
 
-    self.suma_puntos(self.ronda.manojo(&ganador).jugador.equipo, total_pts);
+    if let Some(e) = self.ronda.equipo_de(ganador) {
+      self.suma_puntos(e, total_pts);
+    }
 
     if self.verbose {
       pkts.push(enco::Packet{
         destination: vec![String::from("ALL")],
         message: enco::Message(
           enco::Content::SumaPts {
-            autor: ganador,
+            autor,
             razon: enco::Razon::TrucoQuerido,
             pts: total_pts,
           }
@@ -483,9 +627,9 @@ impl Partida {
   pub fn evaluar_mano(&mut self) -> (bool, Vec<enco::Packet>) {
     let mut pkts: Vec<enco::Packet> = Vec::new();
 
-    let max_poder: HashMap<Equipo, (usize, String)> = HashMap::from([
-      (Equipo::Rojo, (0, String::new())),
-      (Equipo::Azul, (0, String::new())),
+    let max_poder: HashMap<Equipo, (usize, Option<usize>)> = HashMap::from([
+      (Equipo::Rojo, (0, None)),
+      (Equipo::Azul, (0, None)),
     ]);
 
     // let mano = self.ronda.get_mano_actual();
@@ -495,15 +639,15 @@ impl Partida {
     // mano en juego index
     let mej_ix = self.ronda.mano_en_juego as usize;
     
-    let max_poder: HashMap<Equipo, (usize, String)> =
+    let max_poder: HashMap<Equipo, (usize, Option<usize>)> =
       self.ronda.manos[mej_ix].cartas_tiradas
         .iter()
         .fold(max_poder, |mut map, c| {
-            let e = self.ronda.manojo(&c.jugador).jugador.equipo;
+            let e = self.ronda.manojo(c.jugador).jugador.equipo;
             let p = c.carta.calc_poder(&self.ronda.muestra);
             let x = map.entry(e).or_default();
             if p > x.0 {
-              *x = (p,c.jugador.clone())
+              *x = (p, Some(c.jugador))
             }
             map
         });
@@ -512,7 +656,7 @@ impl Partida {
       max_poder.get(&Equipo::Rojo) == max_poder.get(&Equipo::Azul);
 
     let no_se_llego_a_tirar_ninguna_carta = 
-      self.ronda.manos[mej_ix].cartas_tiradas.len() == 0;
+      self.ronda.manos[mej_ix].cartas_tiradas.is_empty();
     let se_fueron_todos = 
       self.ronda.cant_jugadores_en_juego[&Equipo::Rojo] == 0 ||
       self.ronda.cant_jugadores_en_juego[&Equipo::Azul] == 0;
@@ -529,15 +673,13 @@ impl Partida {
           self.ronda.get_mano_actual().resultado = Resultado::GanoAzul;
         }
         if self.ronda.manojos[0].jugador.equipo == equipo_ganador {
-          self.ronda.manos[mej_ix].ganador = 
-            self.ronda.manojos[0].jugador.id.clone();
+          self.ronda.manos[mej_ix].ganador = Some(0);
         } else {
-          self.ronda.manos[mej_ix].ganador = 
-            self.ronda.manojos[1].jugador.id.clone();
+          self.ronda.manos[mej_ix].ganador = Some(1);
         }
       } else if es_parda {
         self.ronda.manos[mej_ix].resultado = Resultado::Empardada;
-        self.ronda.manos[mej_ix].ganador = String::from("");
+        self.ronda.manos[mej_ix].ganador = None;
         if self.verbose {
           pkts.push(enco::Packet{
             destination: vec![String::from("ALL")],
@@ -548,10 +690,10 @@ impl Partida {
         }
       } else {
         if max_poder[&Equipo::Rojo].0 > max_poder[&Equipo::Azul].0 {
-          self.ronda.manos[mej_ix].ganador = max_poder[&Equipo::Rojo].1.clone();
+          self.ronda.manos[mej_ix].ganador = max_poder[&Equipo::Rojo].1;
           self.ronda.manos[mej_ix].resultado = Resultado::GanoRojo;
         } else {
-          self.ronda.manos[mej_ix].ganador = max_poder[&Equipo::Azul].1.clone();
+          self.ronda.manos[mej_ix].ganador = max_poder[&Equipo::Azul].1;
           self.ronda.manos[mej_ix].resultado = Resultado::GanoAzul;
         }
         if self.verbose {
@@ -559,7 +701,9 @@ impl Partida {
             destination: vec![String::from("ALL")],
             message: enco::Message(
               enco::Content::ManoGanada{
-                autor: self.ronda.manos[mej_ix].ganador.clone(),
+                autor: self.ronda.manos[mej_ix].ganador
+                  .map_or("", |g| self.ronda.nombre(g))
+                  .to_string(),
                 valor: self.ronda.mano_en_juego as usize
               }
             )
@@ -577,28 +721,45 @@ impl Partida {
       (empieza_nueva_ronda, pkts)
   }
 
-  pub fn perspectiva(&mut self, jid:&str) -> Partida {
+  // copia de la partida tal como la puede ver `jid`: las cartas ajenas
+  // quedan censuradas como "0 de copa" y se quita todo lo que no puede
+  // saber (ver `censurar`); para los clientes conviene `observar`
+  pub fn perspectiva(&self, jid:&str) -> Result<Partida, Error> {
+    let equipo = self.ronda.get_manojo(jid)?.jugador.equipo;
     let mut copia = self.clone();
-    for mix in 0..copia.ronda.manojos.len() {
-      let m = &copia.ronda.manojos[mix];
-      let hay_que_censurar = 
-        m.jugador.equipo != self.ronda.manojo(jid).jugador.equipo;
-      if hay_que_censurar {
-        for cix in 0..m.cartas.len() {
-          if !copia.ronda.manojos[mix].tiradas[cix] {
-            copia.ronda.manojos[mix].cartas[cix].valor = 0;
-            copia.ronda.manojos[mix].cartas[cix].palo = Palo::Copa;
-          }
-        }
-      }
+    // el historial y las jugadas deshechas tienen las cartas de todos
+    copia.historial = Historial::default();
+    copia.rehechas = Arc::default();
+    censurar(&mut copia.ronda, equipo);
+    for d in copia.duelos.iter_mut() {
+      d.partida.historial = Historial::default();
+      censurar(&mut d.partida.ronda, equipo);
+    }
+    Ok(copia)
+  }
+
+  // copia para las busquedas que la clonan en cada nodo: sin paquetes, sin
+  // el historial ni lo que se puede rehacer, y sin los repartos pendientes
+  // ni el generador (solo sirven para las rondas siguientes)
+  pub(crate) fn para_buscar(&self) -> Partida {
+    let mut ronda = self.ronda.clone();
+    ronda.rng = None;
+    ronda.repartos_pendientes.clear();
+    Partida {
+      puntuacion: self.puntuacion,
+      puntajes: self.puntajes.clone(),
+      ronda,
+      duelos: self.duelos.clone(),
+      verbose: false,
+      historial: Historial::default(),
+      rehechas: Arc::default(),
     }
-    copia
   }
 
   pub fn bye_bye(&self) -> Vec<enco::Packet> {
     let mut pkts: Vec<enco::Packet> = Vec::new();
-    if self.terminada() {
-      if self.verbose {
+    if self.terminada()
+      && self.verbose {
         pkts.push(enco::Packet{
           destination: vec![String::from("ALL")],
           message: enco::Message(
@@ -608,13 +769,12 @@ impl Partida {
           )
         });
       }
-    }
     pkts
   }
 
-  fn parse(&self, cmd: &str) -> Result<Box<dyn IJugada>, &'static str> {
+  pub(crate) fn parse(&self, cmd: &str) -> Result<Box<dyn IJugada>, Error> {
     if self.terminada() {
-      return Err("la partida ya termino")  
+      return Err(Error::PartidaTerminada)
     }
 
     let patt = r"(?i)^([a-zA-Z0-9_-]+) ([a-zA-Z0-9_-]+)$";
@@ -622,21 +782,21 @@ impl Partida {
     if let Some(x) = jugada_simple_rg.captures(cmd) {
       let jugador = x.get(1).unwrap().as_str();
       let jugada = x.get(2).unwrap().as_str();
-      let m = self.ronda.manojo(jugador);
-      let jugada: Box<dyn IJugada> = match jugada {
-        "envido" => Box::new(TocarEnvido{jid: m.jugador.id.clone()}), 
-        "real-envido" => Box::new(TocarRealEnvido{jid: m.jugador.id.clone()}), 
-        "falta-envido" => Box::new(TocarFaltaEnvido{jid: m.jugador.id.clone()}), 
-        "flor" => Box::new(CantarFlor{jid: m.jugador.id.clone()}), 
-        "contra-flor" => Box::new(CantarContraFlor{jid: m.jugador.id.clone()}), 
-        "contra-flor-al-resto" => Box::new(CantarContraFlorAlResto{jid: m.jugador.id.clone()}), 
-        "truco" => Box::new(GritarTruco{jid: m.jugador.id.clone()}), 
-        "re-truco" => Box::new(GritarReTruco{jid: m.jugador.id.clone()}), 
-        "vale-4" => Box::new(GritarVale4{jid: m.jugador.id.clone()}), 
-        "quiero" => Box::new(ResponderQuiero{jid: m.jugador.id.clone()}), 
-        "no-quiero" => Box::new(ResponderNoQuiero{jid: m.jugador.id.clone()}), 
-        "mazo" => Box::new(IrseAlMazo{jid: m.jugador.id.clone()}),
-        _ => unreachable!()
+      let mix = self.ronda.get_mix(jugador)?;
+      let jugada: Box<dyn IJugada> = match jugada.to_lowercase().as_str() {
+        "envido" => Box::new(TocarEnvido{jid: mix}), 
+        "real-envido" => Box::new(TocarRealEnvido{jid: mix}), 
+        "falta-envido" => Box::new(TocarFaltaEnvido{jid: mix}), 
+        "flor" => Box::new(CantarFlor{jid: mix}), 
+        "contra-flor" => Box::new(CantarContraFlor{jid: mix}), 
+        "contra-flor-al-resto" => Box::new(CantarContraFlorAlResto{jid: mix}), 
+        "truco" => Box::new(GritarTruco{jid: mix}), 
+        "re-truco" => Box::new(GritarReTruco{jid: mix}), 
+        "vale-4" => Box::new(GritarVale4{jid: mix}), 
+        "quiero" => Box::new(ResponderQuiero{jid: mix}), 
+        "no-quiero" => Box::new(ResponderNoQuiero{jid: mix}), 
+        "mazo" => Box::new(IrseAlMazo{jid: mix}),
+        _ => return Err(Error::ComandoInvalido(cmd.to_string()))
       };
 
       return Ok(jugada);
@@ -648,30 +808,134 @@ impl Partida {
       let jugador = x.get(1).unwrap().as_str();
       let valor = x.get(2).unwrap().as_str();
       let palo = x.get(3).unwrap().as_str();
-      let _m = self.ronda.manojo(jugador);
+      let mix = self.ronda.get_mix(jugador)?;
+      let m = &self.ronda.manojos[mix];
       let c = Carta::new(
         valor.parse::<i32>().unwrap() as usize,
         palo
-      ).unwrap();
-      let t = TirarCarta{jid: jugador.to_string(), carta: c};
+      )?;
+      if !m.cartas.contains(&c) {
+        return Err(Error::CartaNoEnMano{jid: m.jugador.id.clone(), carta: c});
+      }
+      let t = TirarCarta{jid: mix, carta: c};
       return Ok(Box::new(t));
     }
     
-    Err("algo salio mal")
+    Err(Error::ComandoInvalido(cmd.to_string()))
   }
 
-  pub fn cmd(&mut self, cmd: &str) -> Result<Vec<enco::Packet>, &str> {
-    if let Ok(jugada) = self.parse(cmd) {
-      let mut pkts = jugada.hacer(self);
-      if self.terminada() {
-        if self.verbose {
-          pkts.append(&mut self.bye_bye());
-        }
+  // aplica `jugada` y, si era valida, la registra en el historial junto
+  // con el reparto de la ronda nueva (si es que empezo una)
+  pub fn jugar(&mut self, jugada: &dyn IJugada) -> Vec<enco::Packet> {
+    let (_, ok) = jugada.ok(self);
+    let numero = self.ronda.numero;
+    let mut pkts = jugada.hacer(self);
+    if !ok {
+      return pkts
+    }
+    // una jugada nueva descarta lo deshecho, y con ello los repartos
+    // de las rondas deshechas
+    if !self.rehechas.is_empty() {
+      self.rehechas = Arc::default();
+      self.ronda.repartos_pendientes.clear();
+    }
+    self.historial.jugadas.push(self.comando(jugada));
+    if self.ronda.numero != numero {
+      self.historial.repartos.push(self.ronda.get_reparto());
+    }
+    if self.terminada()
+      && self.verbose {
+        pkts.append(&mut self.bye_bye());
       }
-      return Ok(pkts);
+    pkts
+  }
+
+  // `jugada` en el formato de `cmd`, con el nombre de su jugador
+  pub fn comando(&self, jugada: &dyn IJugada) -> String {
+    format!("{} {}", self.ronda.nombre(jugada.jugada().jid()), jugada.accion())
+  }
+
+  // revierte la ultima jugada aplicada con `jugar` o `cmd` (puntajes,
+  // repartos y estado del envite/truco inclusive) reconstruyendo la partida
+  // desde su historial sin esa jugada, por lo que cuesta lo mismo que
+  // `reproducir`; las aplicadas directo con `IJugada::hacer` no se pueden
+  // deshacer. Retorna false si no habia nada que deshacer
+  pub fn deshacer(&mut self) -> bool {
+    let mut historial = self.historial.clone();
+    let Some(ultima) = historial.jugadas.pop() else {
+      return false
+    };
+    // los repartos de las rondas deshechas quedan pendientes para `rehacer`
+    historial.repartos.extend(self.ronda.repartos_pendientes.iter().cloned());
+    let Ok(mut p) = Partida::desde_historial(&historial) else {
+      return false
+    };
+    if p.aplicar(&historial.jugadas).is_err() {
+      return false
+    }
+    p.ronda.rng = self.ronda.rng.take();
+    p.rehechas = std::mem::take(&mut self.rehechas);
+    Arc::make_mut(&mut p.rehechas).push(ultima);
+    *self = p;
+    true
+  }
+
+  // vuelve a aplicar la ultima jugada deshecha;
+  // aplicar una jugada nueva descarta las que se podian rehacer
+  pub fn rehacer(&mut self) -> bool {
+    let Some(jugada) = self.rehechas.last().cloned() else {
+      return false
+    };
+    // `cmd` descartaria las que quedan por rehacer
+    let mut rehechas = std::mem::take(&mut self.rehechas);
+    let ok = self.cmd(&jugada).is_ok();
+    if ok {
+      Arc::make_mut(&mut rehechas).pop();
     }
-    Err("algo salio mal")
+    self.rehechas = rehechas;
+    ok
+  }
+
+  pub fn cmd(&mut self, cmd: &str) -> Result<Vec<enco::Packet>, Error> {
+    let jugada = self.parse(cmd)?;
+    jugada.validar(self)?;
+    Ok(self.jugar(jugada.as_ref()))
   }
 
 }
 
+// quita de `ronda` lo que no puede saber un jugador de `equipo`:
+// - las cartas no tiradas de los contrarios
+// - quienes de los contrarios tienen flor y no la cantaron aun
+// - el generador y los repartos pendientes (las cartas de las proximas rondas)
+// el resto (mesa, cantos, turnos, mazo) es publico
+fn censurar(ronda: &mut Ronda, equipo: Equipo) {
+  for m in ronda.manojos.iter_mut() {
+    let hay_que_censurar = m.jugador.equipo != equipo;
+    if hay_que_censurar {
+      for cix in 0..m.cartas.len() {
+        if !m.tiradas[cix] {
+          m.cartas[cix].valor = 0;
+          m.cartas[cix].palo = Palo::Copa;
+        }
+      }
+    }
+  }
+  let es_del_equipo = |jid: &usize| ronda.manojos
+    .get(*jid)
+    .is_some_and(|m| m.jugador.equipo == equipo);
+  let sin_cantar = ronda.envite.sin_cantar
+    .iter()
+    .filter(|jid| es_del_equipo(jid))
+    .cloned()
+    .collect();
+  let con_flor = ronda.envite.jugadores_con_flor
+    .iter()
+    .filter(|jid| es_del_equipo(jid))
+    .cloned()
+    .collect();
+  ronda.envite.sin_cantar = sin_cantar;
+  ronda.envite.jugadores_con_flor = con_flor;
+  ronda.rng = None;
+  ronda.repartos_pendientes.clear();
+}
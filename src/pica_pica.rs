@@ -0,0 +1,162 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use crate::enco;
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::historial::{Reparto};
+use crate::jugada::{IJugada, Jugada, error_pkts};
+use crate::partida::{Partida};
+use crate::reglas::{Reglas};
+
+// uno de los 3 mano-a-mano de una ronda de pica-pica: una partida de 2
+// jugadores que arranca con las cartas y los puntajes de la partida
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Duelo {
+  pub partida: Partida,
+  // indices de los 2 jugadores en `Ronda.manojos` de la partida
+  pub mixs: [usize; 2],
+  pub terminado: bool,
+}
+
+impl Partida {
+  pub fn en_pica_pica(&self) -> bool {
+    !self.duelos.is_empty()
+  }
+
+  // el duelo que esta jugando `jid`, si es que no termino
+  pub fn get_duelo(&self, jid: &str) -> Option<&Partida> {
+    let mix = self.ronda.get_mix(jid).ok()?;
+    self.get_duelo_mix(mix).map(|(d, _)| d)
+  }
+
+  // como `get_duelo` pero por indice; retorna ademas el indice de `jid`
+  // dentro del duelo
+  pub fn get_duelo_mix(&self, jid: usize) -> Option<(&Partida, usize)> {
+    self.duelos
+      .iter()
+      .filter(|d| !d.terminado)
+      .find_map(|d| d.mixs
+        .iter()
+        .position(|ix| *ix == jid)
+        .map(|dix| (&d.partida, dix)))
+  }
+
+  // en 6 jugadores se juega una ronda de pica-pica por medio mientras el
+  // que va ganando tenga al menos 5 puntos y le falten mas de 5 para ganar
+  pub fn toca_pica_pica(&self) -> bool {
+    let max = self.get_max_puntaje();
+    self.ronda.reglas.pica_pica &&
+      self.ronda.manojos.len() == 6 &&
+      max >= 5 && max + 5 < self.puntuacion
+  }
+
+  // arma un duelo por cada par de jugadores enfrentados (`i` vs `i+3`)
+  // con las cartas ya repartidas en la ronda actual
+  pub fn armar_duelos(&mut self) -> Result<(), Error> {
+    let n = self.ronda.manojos.len();
+    let reglas = Reglas{pica_pica: false, ..self.ronda.reglas};
+    let mut duelos = Vec::new();
+    for i in 0..n/2 {
+      let j = i + n/2;
+      let (azul, rojo) =
+        if self.ronda.manojos[i].jugador.equipo == Equipo::Azul {(i, j)} else {(j, i)};
+      let reparto = Reparto {
+        muestra: self.ronda.muestra,
+        manojos: vec![self.ronda.manojos[azul].cartas, self.ronda.manojos[rojo].cartas],
+      };
+      // el generador del duelo sale del de la partida, asi la semilla de
+      // la partida determina tambien los duelos
+      let rng = self.ronda.rng
+        .as_mut()
+        .map(|rng| StdRng::seed_from_u64(rng.gen()));
+      let mut partida = Partida::new_con_reparto(
+        self.puntuacion,
+        vec![self.ronda.manojos[azul].jugador.id.clone()],
+        vec![self.ronda.manojos[rojo].jugador.id.clone()],
+        reglas,
+        &reparto,
+        self.verbose,
+        rng,
+      )?;
+      partida.puntajes = self.puntajes.clone();
+      // es mano el primero de los 2 a partir del mano de la ronda
+      let dist = |x: usize| (x + n - self.ronda.el_mano) % n;
+      let el_mano = if dist(azul) < dist(rojo) {0} else {1};
+      partida.ronda.el_mano = el_mano;
+      partida.ronda.turno = el_mano;
+      duelos.push(Duelo{partida, mixs: [azul, rojo], terminado: false});
+    }
+    self.duelos = duelos;
+    Ok(())
+  }
+
+  // aplica `jugada` en el duelo de `jid`; suma a la partida los puntos
+  // que se hagan en el duelo y, cuando terminan los 3, arranca una ronda
+  // normal
+  pub fn hacer_en_duelo(&mut self, jugada: Jugada) -> Vec<enco::Packet> {
+    let jid = jugada.jid();
+    let Some(ix) = self.duelos
+      .iter()
+      .position(|d| !d.terminado && d.mixs.contains(&jid))
+    else {
+      return jugada.ok(self).0
+    };
+
+    let d = &mut self.duelos[ix];
+    let antes = d.partida.puntajes.clone();
+    let numero = d.partida.ronda.numero;
+    // en el duelo `jid` tiene otro indice
+    let dix = if d.mixs[0] == jid {0} else {1};
+    let pkts = jugada.con_jid(dix).hacer(&mut d.partida);
+    d.terminado = d.partida.ronda.numero != numero || d.partida.terminada();
+
+    // los turnos del duelo se traducen a los indices de la partida
+    let mixs = d.mixs;
+    let mut pkts = pkts
+      .into_iter()
+      .filter(|pkt| !matches!(pkt.message.0, enco::Content::NuevaRonda))
+      .map(|mut pkt| {
+        match &mut pkt.message.0 {
+          enco::Content::SigTurno{pos} |
+          enco::Content::SigTurnoPosMano{pos} => *pos = mixs[*pos],
+          _ => (),
+        }
+        pkt
+      })
+      .collect::<Vec<enco::Packet>>();
+
+    let deltas = [Equipo::Azul, Equipo::Rojo]
+      .map(|e| (e, d.partida.puntajes[&e].saturating_sub(antes[&e])));
+    for (e, pts) in deltas {
+      if pts > 0 {
+        self.suma_puntos(e, pts);
+      }
+    }
+    // los demas duelos siguen con los puntajes de la partida (ej. para la
+    // falta envido o para saber si termino)
+    for d in self.duelos.iter_mut() {
+      d.partida.puntajes = self.puntajes.clone();
+    }
+
+    let terminaron_todos = self.duelos.iter().all(|d| d.terminado);
+    if terminaron_todos && !self.terminada() {
+      if let Err(err) = self.nueva_ronda() {
+        pkts.append(&mut error_pkts(self, err));
+        return pkts
+      }
+      if self.verbose {
+        pkts.extend(
+          self.ronda.manojos
+            .iter()
+            .map(|m| enco::Packet{
+              destination: vec![m.jugador.id.clone()],
+              message: enco::Message(enco::Content::NuevaRonda),
+            })
+        );
+      }
+    }
+
+    pkts
+  }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+// variantes de reglamento que se fijan al crear la partida
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct Reglas {
+  // si es false nadie tiene flor: se juega solo con envido
+  pub con_flor: bool,
+  // puntos que separan las malas de las buenas;
+  // `None` es la mitad de la puntuacion
+  pub el_chico: Option<usize>,
+  pub contraflor_al_resto: bool,
+  // en 6 jugadores, alternar rondas de pica-pica (ver `toca_pica_pica`)
+  #[serde(default)]
+  pub pica_pica: bool,
+}
+
+impl Default for Reglas {
+  fn default() -> Reglas {
+    Reglas {
+      con_flor: true,
+      el_chico: None,
+      contraflor_al_resto: true,
+      pica_pica: false,
+    }
+  }
+}
@@ -1,16 +1,22 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::sync::Arc;
 use std::iter;
 use serde::{Deserialize, Serialize};
+use rand::rngs::StdRng;
 
 use crate::enco;
+use crate::error::{Error};
 use crate::mano::{NumMano, Mano, Resultado};
 use crate::jugador::{Jugador};
 use crate::equipo::{Equipo};
 use crate::envite::{Envite};
 use crate::truco::{Truco};
 use crate::manojo::{Manojo};
-use crate::carta::{Carta, get_cartas_random};
+use crate::carta::{Carta, get_cartas_random, get_cartas_random_rng};
+use crate::historial::{Reparto};
+use crate::reglas::{Reglas};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
@@ -31,10 +37,31 @@ use crate::carta::{Carta, get_cartas_random};
   pub muestra: Carta,
   
   pub manos: [Mano; 3],
+
+  // cantidad de rondas jugadas antes que esta
+  #[serde(default)]
+  pub numero: usize,
+  // las de la partida; viven aca porque la ronda las necesita para
+  // saber si se juega con flor
+  #[serde(default)]
+  pub reglas: Reglas,
   
   // otros
+  // no cambia en toda la partida: las copias lo comparten
+  #[serde(skip_deserializing, skip_serializing)]
+  pub mixs: Arc<HashMap<String, usize>>,
+  // generador para los repartos; si es `None` se usa `thread_rng`.
+  // No se serializa: una ronda deserializada reparte con `thread_rng`
+  // hasta que se le asigne otro generador
+  #[serde(skip_deserializing, skip_serializing)]
+  pub rng: Option<StdRng>,
+  // si hay repartos pendientes, `repartir_cartas` los usa en vez de `rng`
+  #[serde(skip_deserializing, skip_serializing)]
+  pub repartos_pendientes: VecDeque<Reparto>,
+  // el motivo por el que `repartir_cartas` rechazo el ultimo reparto
+  // pendiente (en ese caso reparte al azar; ver `Partida::reproducir`)
   #[serde(skip_deserializing, skip_serializing)]
-  pub mixs: HashMap<String, usize>,
+  pub reparto_rechazado: Option<Error>,
 }
 
 // cambio de variable
@@ -46,12 +73,20 @@ fn cv(x: usize, mano: usize, cant_jugadores: usize) -> usize {
   }
 }
 
+// reparte `n` cartas usando `rng` si lo hay, o `thread_rng` si no
+fn get_cartas(n: usize, rng: &mut Option<StdRng>) -> Vec<Carta> {
+  match rng {
+    Some(rng) => get_cartas_random_rng(n, rng),
+    None => get_cartas_random(n),
+  }
+}
+
 // impl<'a> Ronda<'a> { // <- si se usa `jugadores_con_flor` con referencias
 impl Ronda {
 
-  fn check_inputs(azules: &[String], rojos: &[String]) -> Result<(), &'static str> {
+  fn check_inputs(azules: &[String], rojos: &[String]) -> Result<(), Error> {
     // checkeo que no hayan repetidos
-    let uniques = [&azules[..], &rojos[..]]
+    let uniques = [azules, rojos]
       .concat()
       .iter()
       .map(|x| x.as_str())
@@ -60,28 +95,58 @@ impl Ronda {
 
     let ok = azules.len() + rojos.len() == uniques;
     if !ok {
-      return Err("Hay repetidos");
+      return Err(Error::JugadoresRepetidos);
     }
 
     // checkeo que la cantidad de jugadores sea correcta
     let ok = [2,4,6].contains(&uniques) && azules.len() == rojos.len();
     if !ok {
-      return Err("La cantidad de jugadores es inválida");
+      return Err(Error::CantidadDeJugadoresInvalida);
     }
 
     Ok(())
   }
 
-  pub fn new(azules: Vec<String>, rojos: Vec<String>) -> Result<Ronda, &'static str> {
-    if let Err(msg) = Ronda::check_inputs(&azules[..], &rojos[..]) {
-      return Err(msg);
-    }
-    
+  pub fn new(azules: Vec<String>, rojos: Vec<String>) -> Result<Ronda, Error> {
+    Ronda::new_with_rng(azules, rojos, None)
+  }
+
+  // igual que `new` pero todos los repartos (este y los de cada
+  // `nueva_ronda`) salen del generador `rng`
+  pub fn new_with_rng(
+    azules: Vec<String>,
+    rojos: Vec<String>,
+    mut rng: Option<StdRng>,
+  ) -> Result<Ronda, Error> {
+    Ronda::check_inputs(&azules[..], &rojos[..])?;
+
     let cant_jugadores_por_equipo = azules.len();
 
-    // paso a crear los jugadores + manojos(+cartas)
-    let mut cartas = get_cartas_random(cant_jugadores_por_equipo * 2 * 3 + 1);
+    // paso a crear el reparto
+    let mut cartas = get_cartas(cant_jugadores_por_equipo * 2 * 3 + 1, &mut rng);
     let muestra = cartas.pop().unwrap();
+    let manojos = (0..cant_jugadores_por_equipo * 2)
+      .map(|_| [
+        cartas.pop().unwrap(),
+        cartas.pop().unwrap(),
+        cartas.pop().unwrap(),
+      ])
+      .collect::<Vec<[Carta; 3]>>();
+
+    Ronda::new_con_reparto(azules, rojos, &Reparto{muestra, manojos}, rng)
+  }
+
+  // como `new_with_rng` pero con las cartas de `reparto` en vez de
+  // repartir; las rondas siguientes salen de `rng`
+  pub(crate) fn new_con_reparto(
+    azules: Vec<String>,
+    rojos: Vec<String>,
+    reparto: &Reparto,
+    rng: Option<StdRng>,
+  ) -> Result<Ronda, Error> {
+    Ronda::check_inputs(&azules[..], &rojos[..])?;
+
+    let cant_jugadores_por_equipo = azules.len();
 
     let jugadores = 
       azules
@@ -95,50 +160,53 @@ impl Ronda {
         })
         .collect::<Vec<_>>();
     
-    let mixs: HashMap<String, usize> =
+    let mixs: Arc<HashMap<String, usize>> =
       jugadores
         .iter()
         .enumerate()
         .map(|(ix,j)| (j.id.clone(), ix))
-        .collect();
+        .collect::<HashMap<String, usize>>()
+        .into();
 
+    // las cartas las pone `set_reparto`, que ademas valida el reparto
     let manojos = 
       jugadores
         .into_iter()
-        .map(|j| Manojo::new(
-          j,
-          [
-            cartas.pop().unwrap(),
-            cartas.pop().unwrap(),
-            cartas.pop().unwrap(),
-          ]
-        ))
+        .map(|j| Manojo::new(j, [reparto.muestra; 3]))
         .collect::<Vec<Manojo>>();
     
+    let mut ronda = Ronda{
+      mano_en_juego: NumMano::Primera,
+      cant_jugadores_en_juego: HashMap::from([
+        (Equipo::Azul, cant_jugadores_por_equipo),
+        (Equipo::Rojo, cant_jugadores_por_equipo)
+      ]),
+      el_mano: 0,
+      turno:  0,
+      manojos,
+      muestra: reparto.muestra,
+      mixs,
+      envite: Envite::new(Vec::new()),
+      truco: Truco::new(),
+      manos: Default::default(),
+      numero: 0,
+      reglas: Reglas::default(),
+      rng,
+      repartos_pendientes: VecDeque::new(),
+      reparto_rechazado: None,
+    };
+    ronda.set_reparto(reparto)?;
+
     let con_flor = 
-      manojos
+      ronda.manojos
         .iter()
-        .filter(|m| m.tiene_flor(&muestra).0)
-        .map(|m| m.jugador.id.clone())
-        .collect::<Vec<String>>();
-    
-    Ok(
-      Ronda{
-        mano_en_juego: NumMano::Primera,
-        cant_jugadores_en_juego: HashMap::from([
-          (Equipo::Azul, cant_jugadores_por_equipo),
-          (Equipo::Rojo, cant_jugadores_por_equipo)
-        ]),
-        el_mano: 0,
-        turno:  0,
-        manojos: manojos,
-        muestra: muestra,
-        mixs: mixs,
-        envite: Envite::new(con_flor),
-        truco: Truco::new(),
-        manos: Default::default(),
-      }
-    )
+        .enumerate()
+        .filter(|(_, m)| m.tiene_flor(&ronda.muestra).0)
+        .map(|(ix, _)| ix)
+        .collect::<Vec<usize>>();
+    ronda.envite = Envite::new(con_flor);
+
+    Ok(ronda)
   }
 
   pub fn indexar_manojos(&mut self) {
@@ -148,23 +216,32 @@ impl Ronda {
         .map(|m| &m.jugador)
         .enumerate()
         .map(|(ix,j)| (j.id.clone(), ix))
-        .collect();
+        .collect::<HashMap<String, usize>>()
+        .into();
+  }
+
+  // como `Manojo::tiene_flor` pero teniendo en cuenta si se juega con flor
+  pub fn tiene_flor(&self, m: &Manojo) -> (bool, isize) {
+    if !self.reglas.con_flor {
+      return (false, -1)
+    }
+    m.tiene_flor(&self.muestra)
   }
 
   pub fn get_flores(&self) -> (bool, Vec<&Manojo>) {
     let manojos_con_flor = 
       self.manojos
         .iter()
-        .filter(|m| m.tiene_flor(&self.muestra).0)
+        .filter(|m| self.tiene_flor(m).0)
         .collect::<Vec<&Manojo>>();
 
-    (manojos_con_flor.len() > 0, manojos_con_flor)
+    (!manojos_con_flor.is_empty(), manojos_con_flor)
   }
 
   pub fn get_la_flor_mas_alta(&self) -> &Manojo {
     self.manojos
       .iter()
-      .map(|m| (m, m.tiene_flor(&self.muestra).1))
+      .map(|m| (m, self.tiene_flor(m).1))
       // .collect::<Vec<(&Manojo, isize)>>()
       // .into_iter()
       .max_by(|a, b| a.1.cmp(&b.1))
@@ -174,12 +251,9 @@ impl Ronda {
 
   pub fn cachear_flores(&mut self, reset: bool) {
     self.envite.jugadores_con_flor =
-      self
-        .get_flores()
-        .1
-        .iter()
-        .map(|m| m.jugador.id.clone())
-        .collect::<Vec<String>>();
+      (0..self.manojos.len())
+        .filter(|ix| self.tiene_flor(&self.manojos[*ix]).0)
+        .collect::<Vec<usize>>();
     if reset {
       self.envite.sin_cantar = self.envite.jugadores_con_flor.clone();
     }
@@ -189,9 +263,21 @@ impl Ronda {
   // ademas reparte una muestra
   // resetea las `tiradas` y el `se_fue_al_mazo` de cada manojo
   pub fn repartir_cartas(&mut self) {
+    if let Some(reparto) = self.repartos_pendientes.pop_front() {
+      match self.set_reparto(&reparto) {
+        Ok(()) => {
+          for m in &mut self.manojos {
+            m.se_fue_al_mazo = false;
+            m.tiradas.iter_mut().for_each(|i| *i = false);
+          }
+          return;
+        },
+        Err(err) => self.reparto_rechazado = Some(err),
+      }
+    }
     let cant_jugadores = self.manojos.len();
     let num_jugs_por_equipo = cant_jugadores / 2;
-    let mut cartas = get_cartas_random(num_jugs_por_equipo * 2 * 3 + 1);
+    let mut cartas = get_cartas(num_jugs_por_equipo * 2 * 3 + 1, &mut self.rng);
     self.muestra = cartas.pop().unwrap();
     for m in &mut self.manojos {
       m.se_fue_al_mazo = false;
@@ -213,11 +299,47 @@ impl Ronda {
     self.cant_jugadores_en_juego.insert(Equipo::Rojo, num_jugs_por_equipo);
     self.el_mano = el_mano;
     self.turno = el_mano;
+    self.numero += 1;
     self.repartir_cartas();
     self.envite.reset();
     self.cachear_flores(true);
     self.truco.reset();
-    // self.manos = make([]Mano, 3)
+    self.manos = Default::default();
+  }
+
+  // las cartas repartidas en esta ronda
+  pub fn get_reparto(&self) -> Reparto {
+    Reparto {
+      muestra: self.muestra,
+      manojos: self.manojos.iter().map(|m| m.cartas).collect(),
+    }
+  }
+
+  // reemplaza las cartas de esta ronda por las de `reparto`;
+  // no resetea las flores (ver `cachear_flores`)
+  pub fn set_reparto(&mut self, reparto: &Reparto) -> Result<(), Error> {
+    if reparto.manojos.len() != self.manojos.len() {
+      return Err(Error::EstadoInvalido(
+        "el reparto no coincide con la cantidad de jugadores".to_string()));
+    }
+    let cartas = 
+      reparto.manojos
+        .iter()
+        .flatten()
+        .chain(iter::once(&reparto.muestra))
+        .collect::<HashSet<&Carta>>();
+    if cartas.len() != reparto.manojos.len() * 3 + 1 {
+      return Err(Error::EstadoInvalido(
+        "el reparto tiene cartas repetidas".to_string()));
+    }
+    if let Some(c) = cartas.iter().find(|c| !c.es_valida()) {
+      return Err(Error::CartaInvalida(format!("valor {}", c.valor)));
+    }
+    self.muestra = reparto.muestra;
+    for (m, cartas) in self.manojos.iter_mut().zip(reparto.manojos.iter()) {
+      m.cartas = *cartas;
+    }
+    Ok(())
   }
 
   pub fn get_el_mano(&self) -> &Manojo {
@@ -225,9 +347,7 @@ impl Ronda {
   }
 
   pub fn get_sig_el_mano(&self) -> usize {
-    let m = self.get_el_mano();
-    let s = self.get_siguiente(m);
-    self.mixs[&s.jugador.id]
+    self.get_sig(self.el_mano)
   }
 
   pub fn get_el_turno(&self) -> &Manojo {
@@ -248,34 +368,56 @@ impl Ronda {
     if es_el_ultimo {0} else {j + 1}
   }
 
-  pub fn get_siguiente(&self, m: &Manojo) -> &Manojo {
-    let idx = self.mixs[&m.jugador.id];
-    let cant_jugadores = self.manojos.len();
-    let es_el_ultimo = idx == cant_jugadores - 1;
-    match es_el_ultimo {
-      true => &self.manojos[0],
-      false => &self.manojos[idx+1],
-    }
-  }
-
-  pub fn get_siguiente_habilitado<'a>(&'a self, m: &'a Manojo) -> Option<&Manojo> {
-    let mut sig = m;
+  // el siguiente a `j` que sigue en juego y no tiro en esta mano
+  pub fn get_sig_habilitado(&self, j: usize) -> Option<usize> {
+    let mut sig = j;
     let n = self.manojos.len();
     for _ in 0..n {
-      sig = self.get_siguiente(sig);
-      let no_se_fue_al_mazo = !sig.se_fue_al_mazo;
-      let ya_tiro_carta_en_esta_mano = sig.ya_tiro_carta(self.mano_en_juego);
-      let no_es_el = sig.jugador.id != m.jugador.id;
-      let ok = no_se_fue_al_mazo && (!ya_tiro_carta_en_esta_mano) && no_es_el;
+      sig = self.get_sig(sig);
+      let m = &self.manojos[sig];
+      let no_se_fue_al_mazo = !m.se_fue_al_mazo;
+      let ya_tiro_carta_en_esta_mano = m.ya_tiro_carta(self.mano_en_juego);
+      let ok = no_se_fue_al_mazo && (!ya_tiro_carta_en_esta_mano) && sig != j;
       if ok {
         break
       }
     }
-    if sig.jugador.id != m.jugador.id {Some(sig)} else {None}
+    if sig != j {Some(sig)} else {None}
+  }
+
+  pub fn get_manojo(&self, jid: &str) -> Result<&Manojo, Error> {
+    match self.mixs.get(jid) {
+      Some(&mix) => Ok(&self.manojos[mix]),
+      None => Err(Error::JugadorInexistente(jid.to_string())),
+    }
+  }
+
+  // el indice en `manojos` del jugador de nombre `jid`
+  pub fn get_mix(&self, jid: &str) -> Result<usize, Error> {
+    self.mixs
+      .get(jid)
+      .copied()
+      .ok_or_else(|| Error::JugadorInexistente(jid.to_string()))
+  }
+
+  // el nombre del jugador de indice `jid`
+  pub fn nombre(&self, jid: usize) -> &str {
+    self.manojos
+      .get(jid)
+      .map_or("", |m| m.jugador.id.as_str())
+  }
+
+  // el equipo del jugador `jid`, si es que hay alguno
+  pub fn equipo_de(&self, jid: Option<usize>) -> Option<Equipo> {
+    jid
+      .and_then(|ix| self.manojos.get(ix))
+      .map(|m| m.jugador.equipo)
   }
 
-  pub fn manojo(&self, jid: &str) -> &Manojo {
-    &self.manojos[self.mixs[jid]]
+  // PRE: `jid` es un indice valido (para uso interno, una vez que la
+  // jugada ya fue validada; si no, usar `get_manojo`)
+  pub(crate) fn manojo(&self, jid: usize) -> &Manojo {
+    &self.manojos[jid]
   }
 
   /* PREDICADOS */
@@ -290,18 +432,19 @@ impl Ronda {
   pub fn hay_equipo_sin_cantar(&self, equipo: Equipo) -> bool {
     self.envite.sin_cantar
       .iter()
-      .any(|jid| self.manojo(jid).jugador.equipo == equipo)
+      .any(|jid| self.manojo(*jid).jugador.equipo == equipo)
   }
 
   // setters
 
-  pub fn set_next_turno(&mut self) {
-    let manojo_turno_actual = &self.manojos[self.turno];
-    let manojo_sig_turno = self.get_siguiente_habilitado(manojo_turno_actual).unwrap();
-    self.turno = self.mixs[&manojo_sig_turno.jugador.id]
+  pub fn set_next_turno(&mut self) -> Result<(), Error> {
+    self.turno = 
+      self.get_sig_habilitado(self.turno)
+        .ok_or(Error::EstadoInvalido("no hay un siguiente jugador habilitado".to_string()))?;
+    Ok(())
   }
 
-  pub fn set_next_turno_pos_mano(&mut self) {
+  pub fn set_next_turno_pos_mano(&mut self) -> Result<(), Error> {
 
     let sanity_check = |r: &mut Ronda| {
       let candidato = &r.manojos[r.turno];
@@ -313,7 +456,7 @@ impl Ronda {
           let m = &r.manojos[ix];
           let mismo_equipo = m.jugador.equipo == candidato.jugador.equipo;
           if mismo_equipo && !m.se_fue_al_mazo {
-            r.turno = r.mixs[&m.jugador.id];
+            r.turno = ix;
             break
           }
         }
@@ -325,8 +468,9 @@ impl Ronda {
       sanity_check(self);
     } else {
       if self.get_mano_anterior().resultado != Resultado::Empardada {
-        let ganador_anterior = &self.get_mano_anterior().ganador;
-        self.turno = self.mixs[&self.manojo(ganador_anterior).jugador.id];
+        self.turno = self.get_mano_anterior().ganador
+          .filter(|ix| *ix < self.manojos.len())
+          .ok_or(Error::EstadoInvalido("la mano anterior no tiene ganador".to_string()))?;
         sanity_check(self);
       } else {
         let max_tirada = 
@@ -334,19 +478,25 @@ impl Ronda {
             .iter()
             .map(|t| (t, t.carta.calc_poder(&self.muestra)))
             .max_by(|a, b| a.1.cmp(&b.1))
-            .unwrap()
+            .ok_or(Error::EstadoInvalido("la mano anterior no tiene cartas tiradas".to_string()))?
             .0;
-        if !self.manojo(&max_tirada.jugador).se_fue_al_mazo {
-          self.turno = self.mixs[&max_tirada.jugador];
+        let jugador = max_tirada.jugador;
+        let m = self.manojos
+          .get(jugador)
+          .ok_or(Error::JugadorInexistente(jugador.to_string()))?;
+        if !m.se_fue_al_mazo {
+          self.turno = jugador;
           sanity_check(self);
-          return;
+          return Ok(());
         }
 
-        let m = self.get_siguiente_habilitado(self.get_el_mano()).unwrap();
-        self.turno = self.mixs[&m.jugador.id];
+        self.turno = 
+          self.get_sig_habilitado(self.el_mano)
+            .ok_or(Error::EstadoInvalido("no hay un siguiente jugador habilitado".to_string()))?;
         sanity_check(self);
       }
     }
+    Ok(())
   }
 
   pub fn set_manojos(&mut self, manojos: Vec<Manojo>) {
@@ -448,21 +598,21 @@ impl Ronda {
     }
 
     let max_envido = envidos[jidx];
-    return (jidx, max_envido, pkts);
+    (jidx, max_envido, pkts)
   }
 
   pub fn exec_las_flores(&self, a_partir_de:usize, verbose: bool) -> 
     (&Manojo, usize, Vec<enco::Packet>) {
     
     let mut pkts: Vec<enco::Packet> = Vec::new();
-    let equipo = self.manojo(&self.envite.jugadores_con_flor[0]).jugador.equipo.clone();
+    let equipo = self.manojo(self.envite.jugadores_con_flor[0]).jugador.equipo;
     let solo_un_equipo_tiene_flores = 
       self.envite.jugadores_con_flor[1..]
       .iter()
-      .map(|jid| self.manojo(jid))
+      .map(|jid| self.manojo(*jid))
       .any(|m| m.jugador.equipo != equipo);
     if solo_un_equipo_tiene_flores {
-      return (&self.manojo(&self.envite.jugadores_con_flor[0]), 0, pkts);
+      return (self.manojo(self.envite.jugadores_con_flor[0]), 0, pkts);
     }
 
     let flores = 
@@ -475,7 +625,7 @@ impl Ronda {
       self.manojos
         .iter()
         .enumerate()
-        .map(|(i,m)| !(flores[i]>0 && !m.se_fue_al_mazo))
+        .map(|(i,m)| flores[i] <= 0 || m.se_fue_al_mazo)
         .collect::<Vec<_>>();
 
     if flores[a_partir_de] > 0 {
@@ -558,7 +708,7 @@ impl Ronda {
     }
 
     let max_flor = flores[jidx];
-    return (&self.manojos[jidx], max_flor as usize, pkts);
+    (&self.manojos[jidx], max_flor as usize, pkts)
   }  
 
 }
\ No newline at end of file
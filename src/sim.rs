@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::thread;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use crate::chi::{random_action_rng};
+use crate::enco::{Content, Razon};
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::partida::{Partida};
+use crate::reglas::{Reglas};
+
+// configuracion de una simulacion de partidas con jugadas al azar
+#[derive(Debug, Clone)]
+pub struct ConfigSim {
+  pub hilos: usize,
+  pub partidas: usize,
+  pub seed: u64,
+  pub jugadores_por_equipo: usize,
+  pub puntuacion: usize,
+  pub reglas: Reglas,
+  pub allow_mazo: bool,
+}
+
+impl Default for ConfigSim {
+  fn default() -> ConfigSim {
+    ConfigSim {
+      hilos: thread::available_parallelism().map_or(1, |n| n.get()),
+      partidas: 1000,
+      seed: 0,
+      jugadores_por_equipo: 1,
+      puntuacion: 20,
+      reglas: Reglas::default(),
+      allow_mazo: false,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResultadoSim {
+  pub partidas: usize,
+  pub rondas: usize,
+  pub jugadas: usize,
+  pub paquetes: usize,
+  // cuantas veces se ganaron puntos por cada razon
+  pub razones: HashMap<Razon, usize>,
+  pub ganadas: HashMap<Equipo, usize>,
+}
+
+impl ResultadoSim {
+  pub fn rondas_por_partida(&self) -> f64 {
+    self.rondas as f64 / self.partidas.max(1) as f64
+  }
+
+  fn sumar(&mut self, otro: ResultadoSim) {
+    self.partidas += otro.partidas;
+    self.rondas += otro.rondas;
+    self.jugadas += otro.jugadas;
+    self.paquetes += otro.paquetes;
+    for (r, n) in otro.razones {
+      *self.razones.entry(r).or_default() += n;
+    }
+    for (e, n) in otro.ganadas {
+      *self.ganadas.entry(e).or_default() += n;
+    }
+  }
+}
+
+// la semilla de la partida `ix`: depende solo de la semilla de la
+// simulacion, asi el resultado es el mismo con cualquier cantidad de hilos
+pub fn seed_de_partida(seed: u64, ix: usize) -> u64 {
+  seed.wrapping_add(ix as u64)
+}
+
+// juega `config.partidas` partidas repartidas entre `config.hilos` hilos:
+// el hilo `h` juega las partidas `h`, `h + hilos`, `h + 2*hilos`, ...
+pub fn simular(config: &ConfigSim) -> Result<ResultadoSim, Error> {
+  let hilos = config.hilos.clamp(1, config.partidas.max(1));
+  let parciales = thread::scope(|s| {
+    let workers = (0..hilos)
+      .map(|h| s.spawn(move || {
+        let mut res = ResultadoSim::default();
+        for ix in (h..config.partidas).step_by(hilos) {
+          res.sumar(simular_partida(config, seed_de_partida(config.seed, ix))?);
+        }
+        Ok(res)
+      }))
+      .collect::<Vec<_>>();
+    workers
+      .into_iter()
+      .map(|w| w.join().unwrap_or_else(|_| Err(Error::EstadoInvalido("un hilo entro en panico".to_string()))))
+      .collect::<Vec<Result<ResultadoSim, Error>>>()
+  });
+
+  let mut res = ResultadoSim::default();
+  for parcial in parciales {
+    res.sumar(parcial?);
+  }
+  Ok(res)
+}
+
+pub fn simular_partida(config: &ConfigSim, seed: u64) -> Result<ResultadoSim, Error> {
+  let nombres = |equipo: &str| (0..config.jugadores_por_equipo)
+    .map(|i| format!("{}{}", equipo, i))
+    .collect::<Vec<String>>();
+  let mut p = Partida::new_with_reglas_y_rng(
+    config.puntuacion,
+    nombres("azul"),
+    nombres("rojo"),
+    config.reglas,
+    true,
+    Some(StdRng::seed_from_u64(seed)),
+  )?;
+  // otro generador para las jugadas, para no mezclarlo con los repartos
+  let mut rng = StdRng::seed_from_u64(seed ^ 0x9e37_79b9_7f4a_7c15);
+
+  let mut res = ResultadoSim {
+    partidas: 1,
+    ..Default::default()
+  };
+  while !p.terminada() {
+    let a = random_action_rng(&p, config.allow_mazo, &mut rng);
+    let pkts = a.hacer(&mut p);
+    res.jugadas += 1;
+    res.paquetes += pkts.len();
+    for pkt in pkts {
+      if let Content::SumaPts{razon, ..} = pkt.message.0 {
+        *res.razones.entry(razon).or_default() += 1;
+      }
+    }
+  }
+  res.rondas = p.ronda.numero + 1;
+  res.ganadas.insert(p.el_que_va_ganando(), 1);
+  Ok(res)
+}
@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+use std::iter;
+use serde::{Deserialize, Serialize};
+use crate::carta::{Carta};
+use crate::equipo::{Equipo};
+use crate::error::{Error};
+use crate::partida::{Partida};
+
+// version del formato de `Snapshot`; cambiarla ante cualquier cambio
+// incompatible en la serializacion de `Partida`
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Snapshot {
+  pub version: u32,
+  pub partida: Partida,
+}
+
+fn invalido(msg: String) -> Result<(), Error> {
+  Err(Error::EstadoInvalido(msg))
+}
+
+impl Partida {
+  // el generador, el historial y lo que se puede rehacer no se guardan
+  // (ver `cargar`)
+  pub fn guardar(&self) -> Result<String, Error> {
+    let snapshot = Snapshot {
+      version: SNAPSHOT_VERSION,
+      partida: self.clone(),
+    };
+    serde_json::to_string(&snapshot)
+      .map_err(|e| Error::EstadoInvalido(e.to_string()))
+  }
+
+  // carga una partida guardada con `guardar`: reconstruye los indices que
+  // no se serializan y valida que el estado sea consistente.
+  // La partida cargada reparte con `thread_rng` y empieza con el historial
+  // vacio, asi que no se puede `reproducir` ni `deshacer` lo anterior
+  pub fn cargar(json: &str) -> Result<Partida, Error> {
+    let snapshot: Snapshot = serde_json::from_str(json)
+      .map_err(|e| Error::EstadoInvalido(e.to_string()))?;
+    if snapshot.version != SNAPSHOT_VERSION {
+      return Err(Error::VersionIncompatible(snapshot.version));
+    }
+    let mut p = snapshot.partida;
+    for r in iter::once(&mut p.ronda)
+      .chain(p.duelos.iter_mut().map(|d| &mut d.partida.ronda))
+    {
+      r.indexar_manojos();
+    }
+    p.validar()?;
+    for d in p.duelos.iter() {
+      d.partida.validar()?;
+    }
+    Ok(p)
+  }
+
+  // checkea los invariantes de la partida
+  pub fn validar(&self) -> Result<(), Error> {
+    let r = &self.ronda;
+    let n = r.manojos.len();
+
+    if ![2,4,6].contains(&n) {
+      return Err(Error::CantidadDeJugadoresInvalida);
+    }
+    if r.mixs.len() != n {
+      return Err(Error::JugadoresRepetidos);
+    }
+    for (ix, m) in r.manojos.iter().enumerate() {
+      let equipo = if ix % 2 == 0 {Equipo::Azul} else {Equipo::Rojo};
+      if m.jugador.equipo != equipo {
+        return invalido(format!("{} esta sentado en el equipo equivocado", m.jugador.id));
+      }
+    }
+    for e in [Equipo::Azul, Equipo::Rojo] {
+      if !self.puntajes.contains_key(&e) {
+        return invalido(format!("falta el puntaje del equipo {}", e));
+      }
+      let en_juego = r.manojos
+        .iter()
+        .filter(|m| m.jugador.equipo == e && !m.se_fue_al_mazo)
+        .count();
+      if r.cant_jugadores_en_juego.get(&e) != Some(&en_juego) {
+        return invalido(format!("la cantidad de jugadores en juego del equipo {} no coincide", e));
+      }
+    }
+
+    if r.turno >= n {
+      return invalido(format!("turno {} fuera de rango", r.turno));
+    }
+    if r.el_mano >= n {
+      return invalido(format!("el mano {} fuera de rango", r.el_mano));
+    }
+
+    // cartas: todas validas y sin repetir
+    let cartas = r.manojos
+      .iter()
+      .flat_map(|m| m.cartas.iter())
+      .chain(iter::once(&r.muestra))
+      .collect::<Vec<&Carta>>();
+    if let Some(c) = cartas.iter().find(|c| !c.es_valida()) {
+      return Err(Error::CartaInvalida(format!("valor {}", c.valor)));
+    }
+    if cartas.iter().collect::<HashSet<_>>().len() != cartas.len() {
+      return invalido("hay cartas repetidas".to_string());
+    }
+
+    // lo tirado en las manos tiene que coincidir con los manojos
+    for (mix, m) in r.manojos.iter().enumerate() {
+      let tiradas = r.manos
+        .iter()
+        .flat_map(|mano| mano.cartas_tiradas.iter())
+        .filter(|t| t.jugador == mix)
+        .collect::<Vec<_>>();
+      for t in tiradas.iter() {
+        let idx = m.get_carta_idx(&t.carta)?;
+        if !m.tiradas[idx] {
+          return invalido(format!("{} tiro el {} pero no figura como tirada", m.jugador.id, t.carta));
+        }
+      }
+      if tiradas.len() != m.get_cant_cartas_tiradas() {
+        return invalido(format!("la cantidad de cartas tiradas por {} no coincide", m.jugador.id));
+      }
+    }
+
+    // las referencias a jugadores tienen que existir
+    let jugadores = r.manos
+      .iter()
+      .flat_map(|mano| mano.cartas_tiradas.iter().map(|t| t.jugador))
+      .chain(r.manos.iter().filter_map(|mano| mano.ganador))
+      .chain(r.envite.sin_cantar.iter().copied())
+      .chain(r.envite.jugadores_con_flor.iter().copied())
+      .chain(r.envite.cantado_por)
+      .chain(r.truco.cantado_por);
+    for jid in jugadores {
+      if jid >= n {
+        return Err(Error::JugadorInexistente(jid.to_string()));
+      }
+    }
+
+    // y los que tienen flor, seguir en juego con flor
+    for jid in r.envite.jugadores_con_flor.iter() {
+      let m = r.manojo(*jid);
+      if m.se_fue_al_mazo || !r.tiene_flor(m).0 {
+        return invalido(format!("{} no tiene flor en juego", m.jugador.id));
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,122 @@
+use std::sync::OnceLock;
+use crate::carta::{Carta, carta_from_id};
+use crate::equipo::{Equipo};
+use crate::jugador::{Jugador};
+use crate::manojo::{Manojo};
+
+pub(crate) const CANT_CARTAS: usize = 40;
+// combinaciones de 3 cartas distintas: C(40, 3)
+pub(crate) const CANT_MANOJOS: usize = 9880;
+
+// lo que depende solo de (carta, muestra)
+struct TablaCartas {
+  poder: [[u8; CANT_CARTAS]; CANT_CARTAS],
+  puntaje: [[u8; CANT_CARTAS]; CANT_CARTAS],
+  pieza: [[bool; CANT_CARTAS]; CANT_CARTAS],
+}
+
+// lo que depende solo de (manojo, muestra); `tipo_flor` y `flor` valen -1
+// si no tiene flor
+#[derive(Clone, Copy, Default)]
+struct EntradaManojo {
+  tipo_flor: i8,
+  flor: i8,
+  envido: u8,
+}
+
+static CARTAS: OnceLock<Box<TablaCartas>> = OnceLock::new();
+// indexada por `indice_manojo * CANT_CARTAS + muestra.id()`
+static MANOJOS: OnceLock<Vec<EntradaManojo>> = OnceLock::new();
+
+// el id de `c` si es una carta de la baraja (una carta censurada, por
+// ejemplo, no lo es)
+pub(crate) fn id_de(c: &Carta) -> Option<usize> {
+  if c.es_valida() {Some(c.id())} else {None}
+}
+
+// indice de las cartas `ids` en el sistema combinatorio: cada conjunto de
+// 3 cartas distintas tiene un indice en `0..CANT_MANOJOS` sin importar el
+// orden; `None` si hay cartas repetidas
+pub(crate) fn indice_manojo(ids: [usize; 3]) -> Option<usize> {
+  let mut ids = ids;
+  ids.sort_unstable();
+  let [a, b, c] = ids;
+  if a == b || b == c {
+    return None
+  }
+  Some(c * (c - 1) * (c - 2) / 6 + b * (b - 1) / 2 + a)
+}
+
+fn tabla_cartas() -> &'static TablaCartas {
+  CARTAS.get_or_init(|| {
+    let mut t = Box::new(TablaCartas {
+      poder: [[0; CANT_CARTAS]; CANT_CARTAS],
+      puntaje: [[0; CANT_CARTAS]; CANT_CARTAS],
+      pieza: [[false; CANT_CARTAS]; CANT_CARTAS],
+    });
+    for cid in 0..CANT_CARTAS {
+      let c = carta_from_id(cid);
+      for mid in 0..CANT_CARTAS {
+        let m = carta_from_id(mid);
+        t.poder[cid][mid] = c.calc_poder_sin_tabla(&m) as u8;
+        t.puntaje[cid][mid] = c.calc_puntaje_sin_tabla(&m) as u8;
+        t.pieza[cid][mid] = c.es_pieza_sin_tabla(&m);
+      }
+    }
+    t
+  })
+}
+
+fn tabla_manojos() -> &'static [EntradaManojo] {
+  MANOJOS.get_or_init(|| {
+    let mut t = vec![EntradaManojo::default(); CANT_MANOJOS * CANT_CARTAS];
+    for c in 2..CANT_CARTAS {
+      for b in 1..c {
+        for a in 0..b {
+          let ix = indice_manojo([a, b, c]).unwrap();
+          let cartas = [a, b, c].map(carta_from_id);
+          let jugador = Jugador{id: String::new(), equipo: Equipo::Azul};
+          let m = Manojo::new(jugador, cartas);
+          for mid in 0..CANT_CARTAS {
+            let muestra = carta_from_id(mid);
+            let (_, tipo_flor) = m.tiene_flor_sin_tabla(&muestra);
+            t[ix * CANT_CARTAS + mid] = EntradaManojo {
+              tipo_flor: tipo_flor as i8,
+              flor: m.calc_flor_sin_tabla(&muestra) as i8,
+              envido: m.calcular_envido_sin_tabla(&muestra) as u8,
+            };
+          }
+        }
+      }
+    }
+    t
+  })
+}
+
+// fuerza las tablas; si no, se arman la primera vez que se usan
+pub fn inicializar_tablas() {
+  tabla_cartas();
+  tabla_manojos();
+}
+
+pub(crate) fn poder(cid: usize, mid: usize) -> usize {
+  tabla_cartas().poder[cid][mid] as usize
+}
+
+pub(crate) fn puntaje(cid: usize, mid: usize) -> usize {
+  tabla_cartas().puntaje[cid][mid] as usize
+}
+
+pub(crate) fn es_pieza(cid: usize, mid: usize) -> bool {
+  tabla_cartas().pieza[cid][mid]
+}
+
+// `(tipo_flor, flor, envido)` de `m`; `None` si tiene cartas invalidas o
+// repetidas
+pub(crate) fn tabla_manojo(m: &Manojo, muestra: &Carta) -> Option<(isize, isize, usize)> {
+  let mid = id_de(muestra)?;
+  let [a, b, c] = &m.cartas;
+  let ix = indice_manojo([id_de(a)?, id_de(b)?, id_de(c)?])?;
+  let e = tabla_manojos()[ix * CANT_CARTAS + mid];
+  Some((e.tipo_flor as isize, e.flor as isize, e.envido as usize))
+}
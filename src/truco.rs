@@ -30,25 +30,32 @@ impl fmt::Display for EstadoTruco {
 
 impl EstadoTruco {
   pub fn es_truco_respondible(&self) -> bool {
-    [EstadoTruco::Truco, EstadoTruco::ReTruco, EstadoTruco::Vale4].contains(&self)
+    [EstadoTruco::Truco, EstadoTruco::ReTruco, EstadoTruco::Vale4].contains(self)
   }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Truco {
-  pub cantado_por: String,
+  // indice en `Ronda.manojos` del que grito el ultimo
+  pub cantado_por: Option<usize>,
   pub estado: EstadoTruco,
 }
 
+impl Default for Truco {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl Truco {
   pub fn new() -> Truco {
     Truco{
-      cantado_por: String::from(""),
+      cantado_por: None,
       estado: EstadoTruco::NoCantado,
     }
   }
   pub fn reset(&mut self) {
-    self.cantado_por = String::from("");
+    self.cantado_por = None;
     self.estado = EstadoTruco::NoCantado;
   }
 }
\ No newline at end of file
@@ -0,0 +1,103 @@
+use truco::*;
+
+mod common;
+use common::{nueva_con_reglas};
+
+fn c(valor: usize, palo: &str) -> Carta {
+  Carta::new(valor, palo).unwrap()
+}
+
+// alice (mano) contra bob, sin flor y con las cartas dadas
+fn nueva(alice: [Carta; 3], bob: [Carta; 3], muestra: Carta) -> Partida {
+  let mut p = nueva_con_reglas(
+    20, &["alice"], &["bob"], Reglas{con_flor: false, ..Default::default()}, 0);
+  p.ronda.set_reparto(&Reparto{muestra, manojos: vec![alice, bob]}).unwrap();
+  p.ronda.cachear_flores(true);
+  p.historial.repartos = vec![p.ronda.get_reparto()];
+  p
+}
+
+fn fallos_de(a: &Analisis, jid: &str, ix: usize) -> Vec<Fallo> {
+  a.fallos[jid].iter().filter(|f| f.ix == ix).cloned().collect()
+}
+
+#[test]
+fn no_quiso_truco_ganador_test(){
+  // alice tiene las 3 cartas mas altas
+  let mut p = nueva(
+    [c(1, "espada"), c(1, "basto"), c(7, "espada")],
+    [c(4, "copa"), c(5, "copa"), c(6, "oro")],
+    c(3, "basto"),
+  );
+  p.cmd("alice 7 espada").unwrap();
+  p.cmd("bob truco").unwrap();
+  let mut q = p.clone();
+  p.cmd("alice no-quiero").unwrap();
+  q.cmd("alice quiero").unwrap();
+
+  let config = ConfigAnalisis{rollouts: 50, ..Default::default()};
+  let a = analizar(&p.historial, &config).unwrap();
+  let fallos = fallos_de(&a, "alice", 2);
+  assert_eq!(fallos.len(), 1);
+  assert_eq!(fallos[0].tipo, TipoFallo::NoQuiso);
+  assert_eq!(fallos[0].jugada, "alice no-quiero");
+  assert!(fallos[0].perdida > config.umbral);
+
+  // queriendo no hay fallo
+  let a = analizar(&q.historial, &config).unwrap();
+  assert!(fallos_de(&a, "alice", 2).is_empty());
+}
+
+#[test]
+fn envido_no_cantado_test(){
+  // alice tiene 33 de envido y bob 9
+  let mut p = nueva(
+    [c(7, "oro"), c(6, "oro"), c(4, "copa")],
+    [c(4, "espada"), c(5, "copa"), c(12, "basto")],
+    c(3, "basto"),
+  );
+  p.cmd("alice 4 copa").unwrap();
+  p.cmd("bob 5 copa").unwrap();
+
+  let config = ConfigAnalisis{rollouts: 50, ..Default::default()};
+  let a = analizar(&p.historial, &config).unwrap();
+  let fallos = fallos_de(&a, "alice", 0);
+  assert_eq!(fallos.len(), 1);
+  assert_eq!(fallos[0].tipo, TipoFallo::EnviteNoCantado);
+  assert!(fallos[0].mejor.starts_with("alice "));
+}
+
+#[test]
+fn analizar_partida_test(){
+  let mut p = Partida::new_with_seed(
+    10,
+    vec!["alice".to_string()],
+    vec!["bob".to_string()],
+    false,
+    0,
+  ).unwrap();
+  while !p.terminada() {
+    let a = random_action(&p, false);
+    let _ = p.jugar(a.as_ref());
+  }
+
+  let config = ConfigAnalisis{rollouts: 4, umbral: 0.5, seed: 1};
+  let a = analizar(&p.historial, &config).unwrap();
+  assert!(a.decisiones > 0 && a.decisiones <= p.historial.jugadas.len());
+  assert_eq!(a.fallos.len(), 2);
+  for (jid, fallos) in a.fallos.iter() {
+    for f in fallos {
+      assert_eq!(&f.jugador, jid);
+      assert_eq!(f.jugada, p.historial.jugadas[f.ix]);
+      assert!(f.perdida > config.umbral);
+    }
+    assert!(fallos.windows(2).all(|w| w[0].ix < w[1].ix));
+  }
+  // con la misma semilla da lo mismo
+  assert_eq!(analizar(&p.historial, &config).unwrap(), a);
+
+  // un historial roto no se puede analizar
+  let mut h = p.historial.clone();
+  h.jugadas.insert(0, "mallory truco".to_string());
+  assert!(analizar(&h, &config).is_err());
+}
@@ -0,0 +1,200 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use truco::*;
+
+mod common;
+use common::{nueva};
+
+// juega `p` hasta el final: el equipo azul elige con `azul` y el rojo al azar
+fn contra_random<F>(p: &mut Partida, seed: u64, mut azul: F)
+  where F: FnMut(&Partida, &str) -> Jugada
+{
+  let mut rng = StdRng::seed_from_u64(seed);
+  while !p.terminada() {
+    let mix = elegir_actor(p, true).unwrap();
+    let m = &p.ronda.manojos[mix];
+    let jid = m.jugador.id.clone();
+    let jugada = if m.jugador.equipo == Equipo::Azul {
+      azul(p, &jid)
+    } else {
+      let opciones = legales(p, mix, true);
+      opciones[rng.gen_range(0..opciones.len())].clone()
+    };
+    assert!(jugada.ok(p).1, "{} no es valida", p.comando(&jugada));
+    let _ = p.jugar(&jugada);
+  }
+}
+
+#[test]
+fn ismcts_test(){
+  // siempre juega jugadas validas, tambien en 2v2 y con pica-pica
+  let mut bot = Ismcts::new(20, 0);
+  for (azules, rojos) in [
+    (&["alice"][..], &["bob"][..]),
+    (&["alice", "ariana"][..], &["bob", "ben"][..]),
+  ] {
+    let mut p = nueva(azules, rojos, 0);
+    contra_random(&mut p, 0, |p, jid| bot.elegir(p, jid).unwrap());
+  }
+
+  // determinista dada la semilla
+  let p = nueva(&["alice"], &["bob"], 1);
+  let jid = p.ronda.get_el_turno().jugador.id.clone();
+  let a = Ismcts::new(50, 7).elegir(&p, &jid).unwrap();
+  let b = Ismcts::new(50, 7).elegir(&p, &jid).unwrap();
+  assert_eq!(a, b);
+
+  // le gana a random la mayoria de las veces
+  let mut ganadas = 0;
+  for seed in 0..10 {
+    let mut bot = Ismcts::new(100, seed);
+    let mut p = nueva(&["alice"], &["bob"], seed);
+    contra_random(&mut p, seed, |p, jid| bot.elegir(p, jid).unwrap());
+    if p.puntajes[&Equipo::Azul] >= p.puntuacion {
+      ganadas += 1;
+    }
+  }
+  assert!(ganadas >= 7, "gano {} de 10", ganadas);
+}
+
+#[test]
+fn determinizar_con_flor_cantada_test(){
+  let mut p = nueva(&["alice"], &["bob"], 0);
+  let c = |valor, palo| Carta::new(valor, palo).unwrap();
+  p.ronda.manojos[0].cartas = [c(1, "espada"), c(7, "espada"), c(4, "espada")];
+  p.ronda.manojos[1].cartas = [c(3, "oro"), c(6, "oro"), c(12, "oro")];
+  p.ronda.set_muestra(c(1, "copa"));
+
+  p.cmd("alice flor").unwrap();
+
+  // bob no ve las cartas de alice, pero sabe que tiene flor
+  let vista = p.perspectiva("bob").unwrap();
+  for seed in 0..50 {
+    let d = determinizar(&vista, Equipo::Rojo, &mut StdRng::seed_from_u64(seed));
+    let alice = &d.ronda.manojos[0];
+    assert!(alice.tiene_flor(&d.ronda.muestra).0, "seed {}: {:?}", seed, alice.cartas);
+    // la flor de alice ya esta cantada; solo falta la de bob
+    assert_eq!(d.ronda.envite.sin_cantar, vec![1]);
+  }
+}
+
+#[test]
+fn determinizar_sin_flor_test(){
+  let mut p = nueva(&["alice"], &["bob"], 0);
+  let c = |valor, palo| Carta::new(valor, palo).unwrap();
+  p.ronda.manojos[0].cartas = [c(1, "espada"), c(7, "oro"), c(4, "copa")];
+  p.ronda.manojos[1].cartas = [c(3, "oro"), c(6, "basto"), c(12, "copa")];
+  p.ronda.set_muestra(c(5, "espada"));
+  p.ronda.cachear_flores(true);
+
+  p.cmd("alice 1 espada").unwrap();
+
+  // alice tiro sin cantar flor, asi que no tiene
+  let vista = p.perspectiva("bob").unwrap();
+  for seed in 0..200 {
+    let d = determinizar(&vista, Equipo::Rojo, &mut StdRng::seed_from_u64(seed));
+    let alice = &d.ronda.manojos[0];
+    assert!(!alice.tiene_flor(&d.ronda.muestra).0, "seed {}: {:?}", seed, alice.cartas);
+    assert!(!d.ronda.envite.jugadores_con_flor.contains(&0));
+  }
+}
+
+#[test]
+fn heuristico_test(){
+  let bot = Heuristico::new();
+
+  // siempre juega jugadas validas y es determinista
+  for (azules, rojos) in [
+    (&["alice"][..], &["bob"][..]),
+    (&["alice", "ariana"][..], &["bob", "ben"][..]),
+    (&["alice", "ariana", "anna"][..], &["bob", "ben", "bianca"][..]),
+  ] {
+    let mut p = nueva(azules, rojos, 0);
+    contra_random(&mut p, 0, |p, jid| {
+      let a = bot.elegir(p, jid).unwrap();
+      assert_eq!(a, bot.elegir(p, jid).unwrap());
+      a
+    });
+  }
+
+  // con flor, la canta
+  let mut p = nueva(&["alice"], &["bob"], 0);
+  p.ronda.set_reparto(&Reparto {
+    muestra: Carta::new(3, "copa").unwrap(),
+    manojos: vec![
+      [Carta::new(1, "oro").unwrap(), Carta::new(2, "oro").unwrap(), Carta::new(4, "oro").unwrap()],
+      [Carta::new(5, "basto").unwrap(), Carta::new(6, "espada").unwrap(), Carta::new(7, "copa").unwrap()],
+    ],
+  }).unwrap();
+  p.ronda.cachear_flores(true);
+  let (j, razon) = bot.elegir_con_razon(&p, "alice").unwrap();
+  assert_eq!(j.id(), IJugadaId::JIdFlor);
+  assert_eq!(razon, "tiene flor");
+
+  // grita con menos en malas y con mas si el rival esta en malas
+  let c = |valor, palo| Carta::new(valor, palo).unwrap();
+  let grita = |mata: Carta, azul: usize, rojo: usize| {
+    let mut p = nueva(&["alice"], &["bob"], 0);
+    p.ronda.set_reparto(&Reparto {
+      muestra: c(3, "espada"),
+      manojos: vec![
+        [mata, c(4, "basto"), c(5, "copa")],
+        [c(6, "basto"), c(10, "copa"), c(11, "oro")],
+      ],
+    }).unwrap();
+    p.ronda.cachear_flores(true);
+    *p.puntajes.get_mut(&Equipo::Azul).unwrap() = azul;
+    *p.puntajes.get_mut(&Equipo::Rojo).unwrap() = rojo;
+    bot.elegir(&p, "alice").unwrap().id() == IJugadaId::JIdTruco
+  };
+  // el 7 de oro no alcanza para el truco, salvo en malas
+  assert!(grita(c(7, "oro"), 0, 0));
+  assert!(!grita(c(7, "oro"), 12, 12));
+  // el 7 de espada si, salvo que convenga cuidar la ventaja
+  assert!(grita(c(7, "espada"), 12, 12));
+  assert!(!grita(c(7, "espada"), 12, 5));
+
+  // le gana a random la mayoria de las veces
+  let mut ganadas = 0;
+  for seed in 0..20 {
+    let mut p = nueva(&["alice"], &["bob"], seed);
+    contra_random(&mut p, seed, |p, jid| bot.elegir(p, jid).unwrap());
+    if p.puntajes[&Equipo::Azul] >= p.puntuacion {
+      ganadas += 1;
+    }
+  }
+  assert!(ganadas >= 14, "gano {} de 20", ganadas);
+}
+
+// siempre intenta irse al mazo, aunque no este entre las validas
+struct Tramposo {}
+
+impl Bot for Tramposo {
+  fn nombre(&self) -> String {
+    "tramposo".to_string()
+  }
+  fn decidir(&mut self, vista:&Partida, jid:&str, _legales:&[Jugada]) -> Jugada {
+    Jugada::IrseAlMazo(IrseAlMazo{jid: vista.ronda.get_mix(jid).unwrap()})
+  }
+}
+
+#[test]
+fn jugar_partida_test(){
+  for (azules, rojos) in [
+    (&["alice"][..], &["bob"][..]),
+    (&["alice", "ariana", "anna"][..], &["bob", "ben", "bianca"][..]),
+  ] {
+    let mut p = nueva(azules, rojos, 0);
+    let mut bots: [Box<dyn Bot>; 2] = [
+      Box::new(Heuristico::new()),
+      Box::new(Ismcts::new(10, 0)),
+    ];
+    jugar_partida(&mut p, &mut bots).unwrap();
+    assert!(p.terminada());
+    assert!(Partida::reproducir(&p.historial).is_ok());
+  }
+
+  let mut p = nueva(&["alice"], &["bob"], 0);
+  let mut bots: [Box<dyn Bot>; 2] = [Box::new(Tramposo{}), Box::new(Aleatorio::new(0))];
+  assert!(matches!(jugar_partida(&mut p, &mut bots), Err(Error::JugadaIlegal(_))));
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use std::error::Error;
 use truco::*;
 
@@ -125,4 +127,72 @@ fn get_cartas_random_test() {
   //   println!("{}", c)
   // }
   assert!(has_unique_elements(cs))
-}
\ No newline at end of file
+}
+#[test]
+fn carta_error_test() {
+  assert!(matches!(Carta::new(9, "oro"), Err(truco::Error::CartaInvalida(_))));
+  assert!(matches!(Palo::parse("sol"), Err(truco::Error::CartaInvalida(_))));
+}
+
+// clase de `c` segun el reglamento, de la mas fuerte (0) a la mas debil:
+// las piezas, las matas y despues las comunes
+fn clase_ref(c: &Carta, muestra: &Carta) -> usize {
+  let piezas = [2, 4, 5, 11, 10];
+  let matas = [(1, Palo::Espada), (1, Palo::Basto), (7, Palo::Espada), (7, Palo::Oro)];
+  let comunes = [3, 2, 1, 12, 11, 10, 7, 6, 5, 4];
+  // el 12 de la muestra ocupa el lugar de la muestra si esta es pieza
+  let es_doce_pieza = c.valor == 12 && piezas.contains(&muestra.valor);
+  let valor = if es_doce_pieza && c.palo == muestra.palo {muestra.valor} else {c.valor};
+  if c.palo == muestra.palo {
+    if let Some(ix) = piezas.iter().position(|v| *v == valor) {
+      return ix
+    }
+  }
+  if let Some(ix) = matas.iter().position(|m| *m == (c.valor, c.palo)) {
+    return piezas.len() + ix
+  }
+  piezas.len() + matas.len() + comunes.iter().position(|v| *v == c.valor).unwrap()
+}
+
+#[test]
+fn ranking_test() {
+  for muestra in ranking(&Carta::new(1, "oro").unwrap()) {
+    let r = ranking(&muestra);
+    assert!(has_unique_elements(r.iter()));
+    for (a, b) in r.iter().zip(r.iter().skip(1)) {
+      assert!(comparar(a, b, &muestra).is_ge());
+      // los empates quedan juntos y ordenados por id
+      if comparar(a, b, &muestra).is_eq() {
+        assert!(a.id() < b.id());
+      }
+    }
+    for a in r.iter() {
+      for b in r.iter() {
+        let esperado = clase_ref(b, &muestra).cmp(&clase_ref(a, &muestra));
+        assert_eq!(comparar(a, b, &muestra), esperado, "{} vs {} con {}", a, b, muestra);
+      }
+    }
+  }
+
+  // el 12 de la muestra vale lo mismo que la pieza que reemplaza
+  let muestra = Carta::new(2, "oro").unwrap();
+  let doce = Carta::new(12, "oro").unwrap();
+  let cuatro = Carta::new(4, "oro").unwrap();
+  assert_eq!(comparar(&doce, &cuatro, &muestra), std::cmp::Ordering::Greater);
+  assert_eq!(ranking(&muestra)[0..2], [muestra, doce]);
+  assert_eq!(ranking(&muestra)[2], cuatro);
+}
+
+#[test]
+fn primes_test() {
+  assert_eq!(PRIMES.len(), 40);
+  assert!(has_unique_elements(PRIMES.iter()));
+  for p in PRIMES {
+    assert!((2..*p).take_while(|d| d * d <= *p).all(|d| p % d != 0), "{} no es primo", p);
+  }
+  // el 12 de oro ya no choca con el 7 de basto
+  let doce = Carta::new(12, "oro").unwrap();
+  let siete = Carta::new(7, "basto").unwrap();
+  assert_eq!(doce.puid(), 173);
+  assert_ne!(doce.puid(), siete.puid());
+}
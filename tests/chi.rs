@@ -0,0 +1,53 @@
+use truco::*;
+
+mod common;
+use common::{nueva};
+
+#[test]
+fn mascara_test(){
+  for seed in 0..20 {
+    let mut p = nueva(&["alice", "ariana"], &["bob", "ben"], seed);
+
+    while !p.terminada() {
+      for allow_mazo in [true, false] {
+        let mascaras = mascaras(&p, allow_mazo);
+        for (mix, mascara) in mascaras.iter().enumerate() {
+          // la mascara coincide con `chi`
+          let esperado = chi(&p, mix, allow_mazo)
+            .iter()
+            .map(|j| j.jugada())
+            .collect::<Vec<Jugada>>();
+          let obtenido = (0..CANT_ACCIONES)
+            .filter(|ix| mascara & (1 << ix) != 0)
+            .map(|ix| jugada_de_indice(&p, mix, ix).unwrap())
+            .collect::<Vec<Jugada>>();
+          assert_eq!(obtenido, esperado);
+        }
+      }
+      let a = random_action(&p, true);
+      let _ = p.jugar(a.as_ref());
+    }
+  }
+}
+
+#[test]
+fn indices_test(){
+  let p = Partida::new(
+    20,
+    vec!["alice".to_string()],
+    vec!["bob".to_string()],
+    false,
+  ).unwrap();
+  for ix in 0..CANT_ACCIONES {
+    let j = jugada_de_indice(&p, 0, ix).unwrap();
+    assert_eq!(indice_de_jugada(&p, 0, &j), Some(ix));
+    if ix >= 3 {
+      assert_eq!(ix, 2 + j.id() as usize);
+    }
+  }
+  assert!(jugada_de_indice(&p, 0, CANT_ACCIONES).is_none());
+
+  // una jugada de otro jugador no tiene indice para este
+  let otro = jugada_de_indice(&p, 1, 0).unwrap();
+  assert!(indice_de_jugada(&p, 0, &otro).is_none());
+}
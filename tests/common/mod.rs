@@ -0,0 +1,30 @@
+// lo que comparten los tests de integracion; no todos usan todo
+#![allow(dead_code)]
+
+use truco::*;
+
+pub fn ids(xs: &[&str]) -> Vec<String> {
+  xs.iter().map(|x| x.to_string()).collect()
+}
+
+// partida a 20 con las reglas por defecto y el reparto dado por `seed`
+pub fn nueva(azules: &[&str], rojos: &[&str], seed: u64) -> Partida {
+  nueva_con_reglas(20, azules, rojos, Reglas::default(), seed)
+}
+
+pub fn nueva_con_reglas(
+  puntuacion: usize,
+  azules: &[&str],
+  rojos: &[&str],
+  reglas: Reglas,
+  seed: u64,
+) -> Partida {
+  Partida::new_with_reglas_y_rng(
+    puntuacion,
+    ids(azules),
+    ids(rojos),
+    reglas,
+    false,
+    Some(rand::SeedableRng::seed_from_u64(seed)),
+  ).unwrap()
+}
@@ -0,0 +1,138 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use truco::*;
+
+mod common;
+use common::{nueva_con_reglas};
+
+// sin flor, para que nada impida tirar
+fn nueva(azules: &[&str], rojos: &[&str], seed: u64) -> Partida {
+  nueva_con_reglas(20, azules, rojos, Reglas{con_flor: false, ..Default::default()}, seed)
+}
+
+fn cartas_del_turno(p: &Partida) -> Vec<Carta> {
+  let m = p.ronda.get_el_turno();
+  (0..3).filter(|ix| !m.tiradas[*ix]).map(|ix| m.cartas[ix]).collect()
+}
+
+// tira `c` y retorna quien gano la ronda (si termino)
+fn tirar(p: &mut Partida, c: Carta) -> Option<Equipo> {
+  let antes = p.puntajes.clone();
+  let numero = p.ronda.numero;
+  let _ = TirarCarta{jid: p.ronda.turno, carta: c}.hacer(p);
+  if p.ronda.numero == numero && !p.terminada() {
+    return None
+  }
+  [Equipo::Azul, Equipo::Rojo]
+    .into_iter()
+    .find(|e| p.puntajes[e] > antes[e])
+}
+
+// minimax sin podas, por fuerza bruta
+fn fuerza_bruta(p: &Partida) -> Equipo {
+  let equipo = p.ronda.get_el_turno().jugador.equipo;
+  let resultados = cartas_del_turno(p)
+    .into_iter()
+    .map(|c| {
+      let mut q = p.clone();
+      tirar(&mut q, c).unwrap_or_else(|| fuerza_bruta(&q))
+    })
+    .collect::<Vec<Equipo>>();
+  if resultados.contains(&equipo) {equipo} else {equipo.equipo_contrario()}
+}
+
+#[test]
+fn coincide_con_fuerza_bruta_test(){
+  for seed in 0..10 {
+    let mut p = nueva(&["alice"], &["bob"], seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+    // en cada punto de una ronda jugada al azar
+    loop {
+      let evaluaciones = p.evaluar_tiradas().unwrap();
+      assert_eq!(evaluaciones.len(), cartas_del_turno(&p).len());
+      for (c, e) in evaluaciones {
+        let mut q = p.clone();
+        let esperado = tirar(&mut q, c).unwrap_or_else(|| fuerza_bruta(&q));
+        assert_eq!(e, esperado, "seed {} carta {}", seed, c);
+      }
+      let c = *cartas_del_turno(&p).choose(&mut rng).unwrap();
+      if tirar(&mut p, c).is_some() {
+        break
+      }
+    }
+  }
+}
+
+#[test]
+fn el_ganador_gana_contra_cualquiera_test(){
+  for (azules, rojos) in [(&["alice"][..], &["bob"][..]), (&["alice", "ariana"][..], &["bob", "ben"][..])] {
+    for seed in 0..10 {
+      let p = nueva(azules, rojos, seed);
+      let dd = p.doble_dummy().unwrap();
+      assert_eq!(fuerza_bruta(&p), dd.ganador);
+
+      // la linea principal alterna los turnos de la ronda y sus optimas
+      // dan el resultado anunciado
+      for d in dd.decisiones.iter() {
+        let e = p.ronda.manojos[d.jugador].jugador.equipo;
+        let gana = d.evaluaciones.iter().any(|(_, x)| *x == e);
+        assert!(d.evaluaciones.iter().any(|(c, _)| *c == d.optima));
+        assert_eq!(gana, e == dd.ganador);
+      }
+
+      // el ganador tirando la optima le gana a cualquier juego del otro
+      let mut rng = StdRng::seed_from_u64(seed);
+      for _ in 0..5 {
+        let mut q = p.clone();
+        let ganador = loop {
+          let e = q.ronda.get_el_turno().jugador.equipo;
+          let c = if e == dd.ganador {
+            q.doble_dummy().unwrap().decisiones[0].optima
+          } else {
+            *cartas_del_turno(&q).choose(&mut rng).unwrap()
+          };
+          if let Some(g) = tirar(&mut q, c) {
+            break g
+          }
+        };
+        assert_eq!(ganador, dd.ganador);
+      }
+    }
+  }
+}
+
+#[test]
+fn reparto_fijo_test(){
+  // alice tiene las 3 mas altas: gana tire lo que tire
+  let mut p = nueva(&["alice"], &["bob"], 0);
+  p.ronda.set_reparto(&Reparto {
+    muestra: Carta::new(3, "copa").unwrap(),
+    manojos: vec![
+      [Carta::new(1, "espada").unwrap(), Carta::new(1, "basto").unwrap(), Carta::new(7, "espada").unwrap()],
+      [Carta::new(5, "basto").unwrap(), Carta::new(6, "espada").unwrap(), Carta::new(7, "copa").unwrap()],
+    ],
+  }).unwrap();
+  p.ronda.cachear_flores(true);
+  let dd = p.doble_dummy().unwrap();
+  assert_eq!(dd.ganador, Equipo::Azul);
+  // a alice le da lo mismo: tira la mas baja
+  assert_eq!(dd.decisiones[0].jugador, 0);
+  assert_eq!(dd.decisiones[0].optima, Carta::new(7, "espada").unwrap());
+  assert!(dd.decisiones.iter().all(|d| d.evaluaciones.iter().all(|(_, e)| *e == Equipo::Azul)));
+  // 2 manos ganadas por alice
+  assert_eq!(dd.decisiones.len(), 4);
+}
+
+#[test]
+fn doble_dummy_invalido_test(){
+  // con el truco sin responder no se puede tirar
+  let mut p = nueva(&["alice"], &["bob"], 0);
+  p.cmd("alice truco").unwrap();
+  assert!(matches!(p.doble_dummy(), Err(Error::EstadoInvalido(_))));
+  p.cmd("bob quiero").unwrap();
+  assert!(p.doble_dummy().is_ok());
+
+  *p.puntajes.get_mut(&Equipo::Rojo).unwrap() = 20;
+  assert_eq!(p.doble_dummy().unwrap_err(), Error::PartidaTerminada);
+}
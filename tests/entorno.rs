@@ -0,0 +1,88 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use truco::*;
+
+mod common;
+use common::{ids};
+
+// juega una partida entera eligiendo acciones al azar; devuelve las
+// acciones elegidas y el total de recompensas de cada equipo
+fn jugar(e: &mut Entorno, seed: u64) -> (Vec<usize>, [usize; 2]) {
+  let mut rng = StdRng::seed_from_u64(seed);
+  let mut paso = e.reset(seed).unwrap();
+  let mut acciones = Vec::new();
+  let mut total = [0, 0];
+  let mut ronda = [0, 0];
+  let mut inicio_ronda = e.partida.puntajes.clone();
+  let mut rondas = 0;
+  while !paso.terminado {
+    assert_ne!(paso.mascara, 0);
+    assert_eq!(paso.observacion.len(), enco::TENSOR_LEN);
+    let legales = (0..CANT_ACCIONES)
+      .filter(|ix| paso.mascara & (1 << ix) != 0)
+      .collect::<Vec<usize>>();
+    let ix = legales[rng.gen_range(0..legales.len())];
+    acciones.push(ix);
+    let numero = e.partida.ronda.numero;
+    paso = e.step(ix).unwrap();
+    for (i, eq) in [Equipo::Azul, Equipo::Rojo].iter().enumerate() {
+      total[i] += paso.recompensas[eq];
+      ronda[i] += paso.recompensas[eq];
+    }
+
+    // al terminar la ronda se informa lo que gano cada uno en ella, que es
+    // lo que cambiaron los puntajes y la suma de las recompensas
+    let termino_la_ronda = e.partida.ronda.numero != numero || paso.terminado;
+    assert_eq!(paso.recompensas_ronda.is_some(), termino_la_ronda);
+    if let Some(r) = &paso.recompensas_ronda {
+      for (i, eq) in [Equipo::Azul, Equipo::Rojo].iter().enumerate() {
+        assert_eq!(r[eq], ronda[i]);
+        assert_eq!(r[eq], e.partida.puntajes[eq] - inicio_ronda[eq]);
+      }
+      ronda = [0, 0];
+      inicio_ronda = e.partida.puntajes.clone();
+      rondas += 1;
+    }
+  }
+  assert_eq!(ronda, [0, 0]);
+  assert_eq!(rondas, e.partida.ronda.numero + 1);
+  (acciones, total)
+}
+
+#[test]
+fn entorno_test(){
+  let equipos = [
+    (ids(&["alice"]), ids(&["bob"])),
+    (ids(&["alice", "ariana"]), ids(&["bob", "ben"])),
+    (ids(&["alice", "ariana", "anna"]), ids(&["bob", "ben", "bianca"])),
+  ];
+  for (azules, rojos) in equipos {
+    let reglas = Reglas{pica_pica: true, ..Default::default()};
+    let mut e = Entorno::new(20, azules, rojos, reglas, true).unwrap();
+    for seed in 0..5 {
+      let (acciones, total) = jugar(&mut e, seed);
+      // las recompensas suman los puntajes finales
+      assert_eq!(total[0], e.partida.puntajes[&Equipo::Azul]);
+      assert_eq!(total[1], e.partida.puntajes[&Equipo::Rojo]);
+      assert!(e.partida.terminada());
+      assert!(e.step(0).is_err());
+      // las jugadas del entorno no se registran
+      assert!(e.partida.historial.jugadas.is_empty());
+
+      // misma semilla, misma partida
+      let (otras, _) = jugar(&mut e, seed);
+      assert_eq!(acciones, otras);
+    }
+  }
+}
+
+#[test]
+fn step_ilegal_test(){
+  let mut e = Entorno::new(20, ids(&["alice"]), ids(&["bob"]), Reglas::default(), false).unwrap();
+  let paso = e.reset(0).unwrap();
+  let ilegal = (0..CANT_ACCIONES).find(|ix| paso.mascara & (1 << ix) == 0).unwrap();
+  assert!(matches!(e.step(ilegal), Err(Error::JugadaIlegal(_))));
+  assert!(e.step(CANT_ACCIONES).is_err());
+  // no se puede irse al mazo
+  assert_eq!(paso.mascara & (1 << (CANT_ACCIONES - 1)), 0);
+}
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+use truco::*;
+
+#[test]
+fn jugada_test(){
+  let mut p = Partida::new_with_seed(
+    20,
+    vec!["alice".to_string(), "ariana".to_string()],
+    vec!["bob".to_string(), "ben".to_string()],
+    false,
+    3,
+  ).unwrap();
+
+  let mut vistas: HashSet<Jugada> = HashSet::new();
+  for _ in 0..40 {
+    if p.terminada() {
+      break
+    }
+    let jugadas = chis(&p, true)
+      .into_iter()
+      .flatten()
+      .map(|j| j.jugada())
+      .collect::<Vec<Jugada>>();
+
+    for j in jugadas.iter() {
+      // ida y vuelta por json
+      let json = serde_json::to_string(j).unwrap();
+      assert_eq!(&serde_json::from_str::<Jugada>(&json).unwrap(), j);
+      // ida y vuelta por `Box<dyn IJugada>`
+      let b: Box<dyn IJugada> = j.clone().into();
+      assert_eq!(&b.jugada(), j);
+      assert_eq!(b.id(), j.id());
+      assert_eq!(b.accion(), j.accion());
+      assert!(j.ok(&p).1);
+      // `comando` se puede volver a leer con `cmd`
+      let mut q = p.clone();
+      assert!(q.cmd(&p.comando(j)).is_ok(), "{}", p.comando(j));
+      vistas.insert(j.clone());
+    }
+
+    let j = jugadas[jugadas.len() / 2].clone();
+    let _ = p.jugar(&j);
+    // en el historial queda con el nombre del jugador
+    assert_eq!(p.historial.jugadas.last(), Some(&p.comando(&j)));
+    assert!(p.historial.jugadas.last().unwrap().starts_with(p.ronda.nombre(j.jid())));
+  }
+  assert!(vistas.len() > 10);
+
+  let j = Jugada::from(GritarReTruco{jid: 1});
+  assert_eq!(j.id(), IJugadaId::JIdReTruco);
+  assert_eq!(j.jid(), 1);
+  assert_eq!(j.accion(), "re-truco");
+  assert_eq!(p.comando(&j), "bob re-truco");
+  assert_eq!(
+    GritarReTruco::try_from(j.clone()).unwrap(),
+    GritarReTruco{jid: 1}
+  );
+  assert_eq!(j.con_jid(2), Jugada::from(GritarReTruco{jid: 2}));
+  assert_eq!(GritarTruco::try_from(j.clone()).unwrap_err(), j);
+}
+
+#[test]
+fn ids_test(){
+  let jid = || 0;
+  let carta = Carta::new(1, "espada").unwrap();
+  let jugadas: Vec<(Box<dyn IJugada>, IJugadaId)> = vec![
+    (Box::new(TirarCarta{jid: jid(), carta}), IJugadaId::JIdTirarCarta),
+    (Box::new(TocarEnvido{jid: jid()}), IJugadaId::JIdEnvido),
+    (Box::new(TocarRealEnvido{jid: jid()}), IJugadaId::JIdRealEnvido),
+    (Box::new(TocarFaltaEnvido{jid: jid()}), IJugadaId::JIdFaltaEnvido),
+    (Box::new(CantarFlor{jid: jid()}), IJugadaId::JIdFlor),
+    (Box::new(CantarContraFlor{jid: jid()}), IJugadaId::JIdContraFlor),
+    (Box::new(CantarContraFlorAlResto{jid: jid()}), IJugadaId::JIdContraFlorAlResto),
+    (Box::new(GritarTruco{jid: jid()}), IJugadaId::JIdTruco),
+    (Box::new(GritarReTruco{jid: jid()}), IJugadaId::JIdReTruco),
+    (Box::new(GritarVale4{jid: jid()}), IJugadaId::JIdVale4),
+    (Box::new(ResponderQuiero{jid: jid()}), IJugadaId::JIdQuiero),
+    (Box::new(ResponderNoQuiero{jid: jid()}), IJugadaId::JIdNoQuiero),
+    (Box::new(IrseAlMazo{jid: jid()}), IJugadaId::JIdMazo),
+  ];
+  // cada jugada tiene su propio id, igual al de su variante de `Jugada`
+  for (ix, (j, id)) in jugadas.iter().enumerate() {
+    assert_eq!(j.id(), *id, "{:?}", j);
+    assert_eq!(j.jugada().id(), *id, "{:?}", j);
+    assert_eq!(*id as usize, ix);
+  }
+}
+
+#[test]
+fn validar_test(){
+  let mut p = Partida::new_with_seed(
+    20,
+    vec!["alice".to_string()],
+    vec!["bob".to_string()],
+    false,
+    0,
+  ).unwrap();
+  // no es el turno de bob
+  let j = GritarTruco{jid: 1};
+  let err = j.validar(&p).unwrap_err();
+  assert!(matches!(&err, Error::JugadaIlegal(razon) if razon.contains("truco")), "{}", err);
+  // sin verbose no hay paquetes, pero el motivo es el mismo
+  assert_eq!(j.ok(&p), (Vec::new(), false));
+  assert_eq!(p.cmd("bob truco").unwrap_err(), err);
+
+  p.verbose = true;
+  assert_eq!(j.validar(&p).unwrap_err(), err);
+  let (pkts, ok) = j.ok(&p);
+  assert!(!ok);
+  assert_eq!(pkts.len(), 1);
+  assert_eq!(pkts[0].destination, vec!["bob".to_string()]);
+
+  assert_eq!(GritarTruco{jid: 7}.validar(&p), Err(Error::JugadorInexistente("7".to_string())));
+  assert!(GritarTruco{jid: 0}.validar(&p).is_ok());
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::len_zero)]
+
 use truco::*;
 
 #[test]
@@ -22,7 +24,7 @@ fn mano_ix_test(){
 fn mano_struct_test(){
   let mut m = Mano {
     resultado: Resultado::Indeterminado,
-    ganador: String::from(""),
+    ganador: None,
     cartas_tiradas: Vec::new(),
   };
   
@@ -30,7 +32,7 @@ fn mano_struct_test(){
 
   m.agregar_tirada(
     CartaTirada {
-      jugador: String::from("alice"),
+      jugador: 0,
       carta: Carta::new(2, "copa").unwrap()
     }
   );
@@ -42,12 +44,12 @@ fn mano_struct_test(){
 fn mano_json_test(){
   let mut m = Mano {
     resultado: Resultado::Indeterminado,
-    ganador: String::from(""),
+    ganador: None,
     cartas_tiradas: Vec::new(),
   };
   m.agregar_tirada(
     CartaTirada {
-      jugador: String::from("alice"),
+      jugador: 0,
       carta: Carta::new(2, "copa").unwrap()
     }
   );
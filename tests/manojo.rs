@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use truco::*;
 
 #[test]
@@ -70,13 +72,16 @@ fn carta_idx_test() {
   };
 
   let c = Carta::new(3, "copa").unwrap();
-  assert_eq!(2, m.get_carta_idx(&c));
+  assert_eq!(2, m.get_carta_idx(&c).unwrap());
 
   let c = Carta::new(1, "copa").unwrap();
-  assert_eq!(0, m.get_carta_idx(&c));
+  assert_eq!(0, m.get_carta_idx(&c).unwrap());
 
   let c = Carta::new(2, "copa").unwrap();
-  assert_eq!(1, m.get_carta_idx(&c));
+  assert_eq!(1, m.get_carta_idx(&c).unwrap());
+
+  let c = Carta::new(2, "oro").unwrap();
+  assert!(m.get_carta_idx(&c).is_err());
 }
 
 
@@ -217,4 +222,140 @@ fn calc_envido_test() {
     Carta::new(4, "oro").unwrap(),
   ];
   assert_eq!(27, m.calcular_envido(&muestra));
-}
\ No newline at end of file
+}
+// el valor de `c` para el envido y la flor segun el reglamento, y si es pieza
+fn puntaje_ref(c: &Carta, muestra: &Carta) -> (usize, bool) {
+  let piezas = [(2, 30), (4, 29), (5, 28), (10, 27), (11, 27)];
+  let es_doce_pieza = c.valor == 12 && piezas.iter().any(|(v, _)| *v == muestra.valor);
+  let valor = if es_doce_pieza {muestra.valor} else {c.valor};
+  match piezas.iter().find(|(v, _)| *v == valor) {
+    Some((_, pts)) if c.palo == muestra.palo => (*pts, true),
+    _ => (if c.valor <= 7 {c.valor} else {0}, false),
+  }
+}
+
+// con pieza: la pieza mas la mas alta de las otras dos; sin pieza: 20 mas
+// las dos del mismo palo; si no, las dos mas altas
+fn envido_ref(cartas: &[Carta; 3], muestra: &Carta) -> usize {
+  let pts = cartas.map(|c| puntaje_ref(&c, muestra));
+  let pares = [(0, 1), (0, 2), (1, 2)];
+  let pieza = pares
+    .iter()
+    .filter(|(i, j)| pts[*i].1 || pts[*j].1)
+    .map(|(i, j)| pts[*i].0 + pts[*j].0)
+    .max();
+  let mismo_palo = pares
+    .iter()
+    .filter(|(i, j)| cartas[*i].palo == cartas[*j].palo)
+    .map(|(i, j)| pts[*i].0 + pts[*j].0 + 20)
+    .max();
+  let comun = pares
+    .iter()
+    .map(|(i, j)| pts[*i].0 + pts[*j].0)
+    .max();
+  pieza.or(mismo_palo).or(comun).unwrap()
+}
+
+// `(tipo, puntaje)` de la flor con los tipos de `Manojo::tiene_flor`:
+// 1 = dos o mas piezas (la mayor entera y de las otras solo la unidad),
+// 2 = las 3 del mismo palo (20 mas la suma si no hay piezas),
+// 3 = una pieza y las otras 2 del mismo palo
+fn flor_ref(cartas: &[Carta; 3], muestra: &Carta) -> (isize, isize) {
+  let pts = cartas.map(|c| puntaje_ref(&c, muestra));
+  let piezas = pts.iter().filter(|(_, pieza)| *pieza).count();
+  let suma = pts.iter().map(|(p, _)| *p as isize).sum::<isize>();
+  let mismo_palo = |i: usize, j: usize| cartas[i].palo == cartas[j].palo;
+  if piezas >= 2 {
+    let max = pts.iter().map(|(p, _)| *p as isize).max().unwrap();
+    let unidades = pts.iter().map(|(p, _)| *p as isize % 10).sum::<isize>();
+    return (1, max + unidades - max % 10)
+  }
+  if mismo_palo(0, 1) && mismo_palo(1, 2) {
+    return (2, suma + if piezas == 0 {20} else {0})
+  }
+  let otras_mismo_palo = [(0, (1, 2)), (1, (0, 2)), (2, (0, 1))]
+    .iter()
+    .any(|(ix, (i, j))| pts[*ix].1 && mismo_palo(*i, *j));
+  if piezas == 1 && otras_mismo_palo {
+    return (3, suma)
+  }
+  (-1, -1)
+}
+
+#[test]
+fn tabla_de_referencia_test() {
+  let c = |v: usize, p: &str| Carta::new(v, p).unwrap();
+  // (muestra, cartas, envido (sin flor), tipo de flor, flor)
+  let casos = [
+    (c(4, "oro"), [c(2, "oro"), c(1, "oro"), c(7, "basto")], 37, -1, -1),
+    (c(4, "oro"), [c(1, "copa"), c(2, "copa"), c(3, "copa")], 0, 2, 26),
+    (c(4, "oro"), [c(12, "oro"), c(6, "espada"), c(7, "copa")], 36, -1, -1),
+    (c(1, "oro"), [c(12, "oro"), c(6, "espada"), c(7, "copa")], 13, -1, -1),
+    (c(5, "copa"), [c(6, "basto"), c(7, "basto"), c(5, "oro")], 33, -1, -1),
+    (c(5, "copa"), [c(4, "copa"), c(10, "espada"), c(7, "espada")], 0, 3, 36),
+    (c(2, "espada"), [c(10, "espada"), c(12, "espada"), c(3, "basto")], 0, 1, 40),
+    (c(11, "oro"), [c(2, "oro"), c(4, "oro"), c(5, "oro")], 0, 1, 47),
+    (c(1, "espada"), [c(2, "copa"), c(6, "copa"), c(1, "basto")], 28, -1, -1),
+  ];
+  let jugador = Jugador { id: String::from("Alice"), equipo: Equipo::Azul };
+  for (muestra, cartas, envido, tipo, flor) in casos {
+    let m = Manojo::new(jugador.clone(), cartas);
+    assert_eq!(m.tiene_flor(&muestra), (tipo > 0, tipo), "{:?}", cartas);
+    assert_eq!(m.calc_flor(&muestra), flor, "{:?}", cartas);
+    if tipo < 0 {
+      assert_eq!(m.calcular_envido(&muestra), envido, "{:?}", cartas);
+    }
+    assert_eq!(flor_ref(&cartas, &muestra), (tipo, flor));
+  }
+}
+
+#[test]
+fn envido_y_flor_exhaustivo_test() {
+  let baraja = ranking(&Carta::new(1, "oro").unwrap());
+  let jugador = Jugador { id: String::from("Alice"), equipo: Equipo::Azul };
+  for muestra in baraja.iter() {
+    let resto = baraja.iter().filter(|c| *c != muestra).collect::<Vec<&Carta>>();
+    for c in 2..resto.len() {
+      for b in 1..c {
+        for a in 0..b {
+          let cartas = [*resto[a], *resto[b], *resto[c]];
+          let m = Manojo::new(jugador.clone(), cartas);
+          let (tipo, flor) = flor_ref(&cartas, muestra);
+          assert_eq!(m.tiene_flor(muestra), (tipo > 0, tipo), "{:?} con {}", cartas, muestra);
+          assert_eq!(m.calc_flor(muestra), flor, "{:?} con {}", cartas, muestra);
+          if tipo < 0 {
+            assert_eq!(
+              m.calcular_envido(muestra),
+              envido_ref(&cartas, muestra),
+              "{:?} con {}", cartas, muestra
+            );
+          }
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn clave_test() {
+  let baraja = ranking(&Carta::new(1, "oro").unwrap());
+  let jugador = Jugador { id: String::from("Alice"), equipo: Equipo::Azul };
+  let mut claves = std::collections::HashSet::new();
+  let mut con_muestra = std::collections::HashSet::new();
+  for c in 2..baraja.len() {
+    for b in 1..c {
+      for a in 0..b {
+        let m = Manojo::new(jugador.clone(), [baraja[a], baraja[b], baraja[c]]);
+        assert!(claves.insert(m.clave()));
+        // no depende del orden
+        let otro = Manojo::new(jugador.clone(), [baraja[c], baraja[a], baraja[b]]);
+        assert_eq!(otro.clave(), m.clave());
+        for muestra in baraja.iter().filter(|x| !m.cartas.contains(x)) {
+          assert!(con_muestra.insert(m.clave_con_muestra(muestra)));
+        }
+      }
+    }
+  }
+  assert_eq!(claves.len(), 9880);
+  assert_eq!(con_muestra.len(), 9880 * 37);
+}
@@ -0,0 +1,150 @@
+use truco::*;
+
+#[test]
+fn observacion_test(){
+  let mut p = Partida::new_with_seed(
+    20,
+    vec!["alice".to_string(), "ariana".to_string()],
+    vec!["bob".to_string(), "ben".to_string()],
+    false,
+    1,
+  ).unwrap();
+  assert!(p.observar("mallory").is_err());
+
+  for _ in 0..6 {
+    let a = random_action(&p, false);
+    let _ = p.jugar(a.as_ref());
+  }
+
+  for m in p.ronda.manojos.iter() {
+    let jid = &m.jugador.id;
+    let o = p.observar(jid).unwrap();
+    assert_eq!(&o.jid, jid);
+    assert_eq!(o.muestra, p.ronda.muestra);
+    assert_eq!(o.puntajes, p.puntajes);
+    for (om, pm) in o.manojos.iter().zip(p.ronda.manojos.iter()) {
+      for ix in 0..3 {
+        // ve su mano entera y de los demas solo lo tirado
+        if om.jugador.id == *jid || pm.tiradas[ix] {
+          assert_eq!(om.cartas[ix], Some(pm.cartas[ix]));
+        } else {
+          assert_eq!(om.cartas[ix], None);
+        }
+      }
+    }
+    assert_eq!(o.flor_pendiente, p.ronda.envite.sin_cantar.contains(&p.ronda.mixs[jid]));
+
+    // las cartas ocultas se serializan como `null`, nunca como "0 de copa"
+    let json = serde_json::to_string(&o).unwrap();
+    assert!(!json.contains(r#""valor":0"#));
+    let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(v["envite"].get("sin_cantar").is_none());
+    let q: Observacion = serde_json::from_str(&json).unwrap();
+    assert_eq!(q.manojos[0].cartas, o.manojos[0].cartas);
+  }
+}
+
+fn c(valor: usize, palo: &str) -> Carta {
+  Carta::new(valor, palo).unwrap()
+}
+
+#[test]
+fn observacion_companero_test(){
+  let p = Partida::new_with_seed(
+    20,
+    vec!["alice".to_string(), "ariana".to_string()],
+    vec!["bob".to_string(), "ben".to_string()],
+    false,
+    3,
+  ).unwrap();
+  // a diferencia de `perspectiva`, tampoco ve las cartas del companero
+  let o = p.observar("alice").unwrap();
+  let vista = p.perspectiva("alice").unwrap();
+  let ariana = p.ronda.get_mix("ariana").unwrap();
+  assert_eq!(vista.ronda.manojos[ariana].cartas, p.ronda.manojos[ariana].cartas);
+  assert_eq!(o.manojos[ariana].cartas, [None; 3]);
+  for m in o.manojos.iter().filter(|m| m.jugador.id != "alice") {
+    assert_eq!(m.cartas, [None; 3]);
+  }
+}
+
+#[test]
+fn observacion_flor_test(){
+  let mut p = Partida::new_with_seed(
+    20,
+    vec!["alice".to_string(), "ariana".to_string()],
+    vec!["bob".to_string(), "ben".to_string()],
+    false,
+    0,
+  ).unwrap();
+  // solo bob tiene flor
+  p.ronda.set_reparto(&Reparto{
+    muestra: c(4, "basto"),
+    manojos: vec![
+      [c(4, "oro"), c(5, "espada"), c(6, "basto")],
+      [c(1, "copa"), c(2, "copa"), c(3, "copa")],
+      [c(7, "oro"), c(10, "espada"), c(11, "copa")],
+      [c(12, "oro"), c(1, "espada"), c(7, "basto")],
+    ],
+  }).unwrap();
+  p.ronda.cachear_flores(true);
+
+  // la flor que un rival no canto no se ve
+  for jid in ["alice", "ariana", "ben"] {
+    let o = p.observar(jid).unwrap();
+    assert!(!o.flor_pendiente);
+    let json = serde_json::to_string(&o).unwrap();
+    assert!(!json.contains("sin_cantar"));
+    assert!(!json.contains("jugadores_con_flor"));
+  }
+  assert!(p.observar("bob").unwrap().flor_pendiente);
+
+  // cuando la canta deja de estar pendiente y todos ven los puntos
+  p.cmd("alice 4 oro").unwrap();
+  p.cmd("bob flor").unwrap();
+  assert!(p.ronda.envite.sin_cantar.is_empty());
+  for jid in ["alice", "bob"] {
+    let o = p.observar(jid).unwrap();
+    assert!(!o.flor_pendiente);
+    assert_eq!(o.puntajes[&Equipo::Rojo], 3);
+  }
+}
+
+#[test]
+fn observacion_duelo_test(){
+  let mut p = Partida::new_with_reglas_y_rng(
+    30,
+    vec!["alice".to_string(), "ariana".to_string(), "anna".to_string()],
+    vec!["bob".to_string(), "ben".to_string(), "bianca".to_string()],
+    Reglas{pica_pica: true, ..Default::default()},
+    false,
+    Some(rand::SeedableRng::seed_from_u64(0)),
+  ).unwrap();
+  assert!(p.observar("alice").unwrap().duelo.is_none());
+
+  *p.puntajes.get_mut(&Equipo::Azul).unwrap() = 7;
+  p.armar_duelos().unwrap();
+
+  // en pica-pica cada uno ve ademas su duelo, con su mano y nada del rival
+  let alice = p.ronda.get_mix("alice").unwrap();
+  let o = p.observar("alice").unwrap();
+  let d = o.duelo.as_ref().unwrap();
+  assert_eq!(d.jid, "alice");
+  assert_eq!(d.manojos.len(), 2);
+  assert!(d.duelo.is_none());
+  for m in d.manojos.iter() {
+    if m.jugador.id == "alice" {
+      assert_eq!(m.cartas, p.ronda.manojos[alice].cartas.map(Some));
+    } else {
+      assert_eq!(m.cartas, [None; 3]);
+    }
+  }
+
+  // cuando termina su duelo ya no lo ve, pero los demas si
+  let turno = p.get_duelo("alice").unwrap().ronda.get_el_turno().jugador.id.clone();
+  p.cmd(&format!("{} mazo", turno)).unwrap();
+  assert!(p.en_pica_pica());
+  assert!(p.observar("alice").unwrap().duelo.is_none());
+  assert!(p.observar(&turno).unwrap().duelo.is_none());
+  assert!(p.observar("ariana").unwrap().duelo.is_some());
+}
@@ -26,4 +26,88 @@ fn message_json_test(){
 
   let _json = serde_json::to_string(&p).unwrap();
   println!("the json for packet is: {}", _json);
-}
\ No newline at end of file
+}
+#[test]
+fn message_deserialize_test(){
+  let autor = || String::from("alice");
+  // cada variante con el json exacto que manda el servidor
+  let casos = vec![
+    (enco::Content::LaManoResultaParda,
+      r#"{"cod":"LaManoResultaParda"}"#),
+    (enco::Content::Error{msg: String::from("teletubbies")},
+      r#"{"cod":"Error","cont":{"msg":"teletubbies"}}"#),
+    (enco::Content::ByeBye{msg: String::from("Azul")},
+      r#"{"cod":"ByeBye","cont":{"msg":"Azul"}}"#),
+    (enco::Content::DiceSonBuenas{autor: autor()},
+      r#"{"cod":"DiceSonBuenas","cont":{"autor":"alice"}}"#),
+    (enco::Content::CantarFlor{autor: autor()},
+      r#"{"cod":"CantarFlor","cont":{"autor":"alice"}}"#),
+    (enco::Content::CantarContraFlor{autor: autor()},
+      r#"{"cod":"CantarContraFlor","cont":{"autor":"alice"}}"#),
+    (enco::Content::CantarContraFlorAlResto{autor: autor()},
+      r#"{"cod":"CantarContraFlorAlResto","cont":{"autor":"alice"}}"#),
+    (enco::Content::TocarEnvido{autor: autor()},
+      r#"{"cod":"TocarEnvido","cont":{"autor":"alice"}}"#),
+    (enco::Content::TocarRealEnvido{autor: autor()},
+      r#"{"cod":"TocarRealEnvido","cont":{"autor":"alice"}}"#),
+    (enco::Content::TocarFaltaEnvido{autor: autor()},
+      r#"{"cod":"TocarFaltaEnvido","cont":{"autor":"alice"}}"#),
+    (enco::Content::GritarTruco{autor: autor()},
+      r#"{"cod":"GritarTruco","cont":{"autor":"alice"}}"#),
+    (enco::Content::GritarReTruco{autor: autor()},
+      r#"{"cod":"GritarReTruco","cont":{"autor":"alice"}}"#),
+    (enco::Content::GritarVale4{autor: autor()},
+      r#"{"cod":"GritarVale4","cont":{"autor":"alice"}}"#),
+    (enco::Content::NoQuiero{autor: autor()},
+      r#"{"cod":"NoQuiero","cont":{"autor":"alice"}}"#),
+    (enco::Content::ConFlorMeAchico{autor: autor()},
+      r#"{"cod":"ConFlorMeAchico","cont":{"autor":"alice"}}"#),
+    (enco::Content::QuieroTruco{autor: autor()},
+      r#"{"cod":"QuieroTruco","cont":{"autor":"alice"}}"#),
+    (enco::Content::QuieroEnvite{autor: autor()},
+      r#"{"cod":"QuieroEnvite","cont":{"autor":"alice"}}"#),
+    (enco::Content::Mazo{autor: autor()},
+      r#"{"cod":"Mazo","cont":{"autor":"alice"}}"#),
+    (enco::Content::ElEnvidoEstaPrimero{autor: autor()},
+      r#"{"cod":"ElEnvidoEstaPrimero","cont":{"autor":"alice"}}"#),
+    (enco::Content::Abandono{autor: autor()},
+      r#"{"cod":"Abandono","cont":{"autor":"alice"}}"#),
+    (enco::Content::SigTurno{pos: 1},
+      r#"{"cod":"SigTurno","cont":{"pos":1}}"#),
+    (enco::Content::SigTurnoPosMano{pos: 2},
+      r#"{"cod":"SigTurnoPosMano","cont":{"pos":2}}"#),
+    (enco::Content::DiceTengo{autor: autor(), valor: 27},
+      r#"{"cod":"DiceTengo","cont":{"autor":"alice","valor":27}}"#),
+    (enco::Content::DiceSonMejores{autor: autor(), valor: 33},
+      r#"{"cod":"DiceSonMejores","cont":{"autor":"alice","valor":33}}"#),
+    (enco::Content::ManoGanada{autor: autor(), valor: 1},
+      r#"{"cod":"ManoGanada","cont":{"autor":"alice","valor":1}}"#),
+    (enco::Content::RondaGanada{autor: autor(), razon: enco::Razon::SeFueronAlMazo},
+      r#"{"cod":"RondaGanada","cont":{"autor":"alice","razon":"sefueronalmazo"}}"#),
+    (enco::Content::NuevaPartida,
+      r#"{"cod":"NuevaPartida","cont":null}"#),
+    (enco::Content::NuevaRonda,
+      r#"{"cod":"NuevaRonda","cont":null}"#),
+    (enco::Content::TirarCarta{autor: autor(), palo: String::from("Oro"), valor: 12},
+      r#"{"cod":"TirarCarta","cont":{"autor":"alice","palo":"Oro","valor":12}}"#),
+    (enco::Content::SumaPts{autor: autor(), razon: enco::Razon::ContraFlorAlRestoGanada, pts: 4},
+      r#"{"cod":"SumaPts","cont":{"autor":"alice","razon":"contrafloralrestoganada","pts":4}}"#),
+  ];
+
+  for (content, json) in casos {
+    let m = enco::Message(content);
+    assert_eq!(serde_json::to_string(&m).unwrap(), json);
+    assert_eq!(serde_json::from_str::<enco::Message>(json).unwrap(), m);
+
+    let p = enco::Packet{
+      destination: vec![String::from("ALL")],
+      message: m,
+    };
+    let json = serde_json::to_string(&p).unwrap();
+    let q: enco::Packet = serde_json::from_str(&json).unwrap();
+    assert_eq!(p, q);
+  }
+
+  assert!(serde_json::from_str::<enco::Message>(r#"{"cod":"Bailar"}"#).is_err());
+  assert!(serde_json::from_str::<enco::Message>(r#"{"cod":"Mazo","cont":{}}"#).is_err());
+}
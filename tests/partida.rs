@@ -2,7 +2,7 @@ use truco::*;
 
 #[test]
 fn partida_test(){
-  let json = r#"{"puntuacion":20,"puntajes":{"azul":0,"rojo":0},"ronda":{"mano_en_juego":"primera","cant_jugadores_en_juego":{"azul":1,"rojo":1},"el_mano":0,"turno":0,"envite":{"estado":"deshabilitado","puntaje":0,"cantado_por":"","sin_cantar":[]},"truco":{"cantado_por":"alice","estado":"vale4"},"manojos":[{"se_fue_al_mazo":false,"cartas":[{"valor":5,"palo":"espada"},{"valor":1,"palo":"oro"},{"valor":4,"palo":"basto"}],"tiradas":[true,true,true],"ultima_tirada":0,"jugador":{"id":"alice","equipo":"azul"}},{"se_fue_al_mazo":false,"cartas":[{"valor":5,"palo":"basto"},{"valor":3,"palo":"basto"},{"valor":7,"palo":"espada"}],"tiradas":[false,true,false],"ultima_tirada":1,"jugador":{"id":"bob","equipo":"rojo"}}],"muestra":{"valor":4,"palo":"oro"},"manos":[{"resultado":"ganoRojo","ganador":"bob","cartas_tiradas":[{"jugador":"alice","carta":{"valor":4,"palo":"basto"}},{"jugador":"bob","carta":{"valor":3,"palo":"basto"}},{"jugador":"alice","carta":{"valor":1,"palo":"oro"}},{"jugador":"alice","carta":{"valor":5,"palo":"espada"}}]},{"resultado":"indeterminado","ganador":"","cartas_tiradas":[]},{"resultado":"indeterminado","ganador":"","cartas_tiradas":[]}]}}"#;
+  let json = r#"{"puntuacion":20,"puntajes":{"azul":0,"rojo":0},"ronda":{"mano_en_juego":"primera","cant_jugadores_en_juego":{"azul":1,"rojo":1},"el_mano":0,"turno":0,"envite":{"estado":"deshabilitado","puntaje":0,"cantado_por":null,"sin_cantar":[]},"truco":{"cantado_por":0,"estado":"vale4"},"manojos":[{"se_fue_al_mazo":false,"cartas":[{"valor":5,"palo":"espada"},{"valor":1,"palo":"oro"},{"valor":4,"palo":"basto"}],"tiradas":[true,true,true],"ultima_tirada":0,"jugador":{"id":"alice","equipo":"azul"}},{"se_fue_al_mazo":false,"cartas":[{"valor":5,"palo":"basto"},{"valor":3,"palo":"basto"},{"valor":7,"palo":"espada"}],"tiradas":[false,true,false],"ultima_tirada":1,"jugador":{"id":"bob","equipo":"rojo"}}],"muestra":{"valor":4,"palo":"oro"},"manos":[{"resultado":"ganoRojo","ganador":1,"cartas_tiradas":[{"jugador":0,"carta":{"valor":4,"palo":"basto"}},{"jugador":1,"carta":{"valor":3,"palo":"basto"}},{"jugador":0,"carta":{"valor":1,"palo":"oro"}},{"jugador":0,"carta":{"valor":5,"palo":"espada"}}]},{"resultado":"indeterminado","ganador":null,"cartas_tiradas":[]},{"resultado":"indeterminado","ganador":null,"cartas_tiradas":[]}]}}"#;
   let p: Partida = serde_json::from_str(json).unwrap();
   println!("{:?}", p)
 }
@@ -10,11 +10,13 @@ fn partida_test(){
 #[test]
 fn cmd_test(){
   
-  let mut p = Partida::new(
+  // con semilla fija: si alice tuviera flor no podria gritar truco
+  let mut p = Partida::new_with_seed(
     20, 
     vec!["alice".to_string()],
     vec!["bob".to_string()],
     false,
+    0,
   ).unwrap();
   
   println!("{:?}", p);
@@ -23,4 +25,423 @@ fn cmd_test(){
 
   println!("{:?}", p);
   println!("{}", 33)
-}
\ No newline at end of file
+}
+#[test]
+fn seed_test(){
+  let nueva = |seed: u64| Partida::new_with_seed(
+    20,
+    vec!["alice".to_string()],
+    vec!["bob".to_string()],
+    false,
+    seed,
+  ).unwrap();
+
+  let mut p = nueva(42);
+  let mut q = nueva(42);
+  assert_eq!(p.ronda.muestra, q.ronda.muestra);
+
+  // mismas jugadas sobre ambas -> mismos repartos en cada `nueva_ronda`
+  let mut rondas = 0;
+  while !p.terminada() {
+    let el_mano = p.ronda.el_mano;
+    let a = random_action(&p, false);
+    let _ = a.hacer(&mut p);
+    let _ = a.hacer(&mut q);
+    if p.ronda.el_mano != el_mano {
+      rondas += 1;
+    }
+    assert_eq!(p.ronda.muestra, q.ronda.muestra);
+    for (m, n) in p.ronda.manojos.iter().zip(q.ronda.manojos.iter()) {
+      assert_eq!(m.cartas, n.cartas);
+    }
+  }
+  assert!(rondas > 0);
+  assert_eq!(p.puntajes, q.puntajes);
+
+  // otra semilla -> otro reparto (con altisima probabilidad)
+  let r = nueva(43);
+  let cartas = |p: &Partida| p.ronda.manojos
+    .iter()
+    .flat_map(|m| m.cartas.to_vec())
+    .collect::<Vec<Carta>>();
+  assert_ne!(cartas(&nueva(42)), cartas(&r));
+}
+
+#[test]
+fn errores_test(){
+  let nueva = |puntuacion: usize, azules: Vec<&str>, rojos: Vec<&str>| Partida::new(
+    puntuacion,
+    azules.into_iter().map(String::from).collect(),
+    rojos.into_iter().map(String::from).collect(),
+    false,
+  );
+
+  assert_eq!(
+    nueva(0, vec!["alice"], vec!["bob"]).unwrap_err(),
+    Error::PuntuacionInvalida(0)
+  );
+  assert_eq!(
+    nueva(20, vec!["alice", "ana"], vec!["bob"]).unwrap_err(),
+    Error::CantidadDeJugadoresInvalida
+  );
+  assert_eq!(
+    nueva(20, vec!["alice"], vec!["alice"]).unwrap_err(),
+    Error::JugadoresRepetidos
+  );
+
+  let mut p = nueva(20, vec!["alice"], vec!["bob"]).unwrap();
+  assert_eq!(
+    p.cmd("mallory truco").unwrap_err(),
+    Error::JugadorInexistente("mallory".to_string())
+  );
+  assert!(matches!(p.cmd("alice"), Err(Error::ComandoInvalido(_))));
+  assert!(matches!(p.cmd("alice bailar"), Err(Error::ComandoInvalido(_))));
+  // no es el turno de bob
+  assert!(matches!(p.cmd("bob truco"), Err(Error::JugadaIlegal(_))));
+
+  // una carta que alice no tiene
+  let ajena = p.ronda.manojos[1].cartas[0];
+  let cmd = format!("alice {} {}", ajena.valor, ajena.palo);
+  assert_eq!(
+    p.cmd(&cmd).unwrap_err(),
+    Error::CartaNoEnMano{jid: "alice".to_string(), carta: ajena}
+  );
+
+  *p.puntajes.get_mut(&Equipo::Rojo).unwrap() = 20;
+  assert_eq!(p.cmd("alice mazo").unwrap_err(), Error::PartidaTerminada);
+}
+
+#[test]
+fn truco_con_flor_sin_cantar_test(){
+  let mut p = Partida::new_with_seed(
+    20,
+    vec!["alice".to_string()],
+    vec!["bob".to_string()],
+    false,
+    0,
+  ).unwrap();
+  // alice tiene flor: primero la tiene que cantar
+  p.ronda.set_muestra(Carta::new(4, "copa").unwrap());
+  p.ronda.manojos[0].cartas = [
+    Carta::new(1, "oro").unwrap(),
+    Carta::new(2, "oro").unwrap(),
+    Carta::new(3, "oro").unwrap(),
+  ];
+  p.ronda.manojos[1].cartas = [
+    Carta::new(5, "basto").unwrap(),
+    Carta::new(6, "espada").unwrap(),
+    Carta::new(7, "copa").unwrap(),
+  ];
+  p.ronda.cachear_flores(true);
+
+  assert!(matches!(p.cmd("alice truco"), Err(Error::JugadaIlegal(_))));
+  assert!(matches!(p.cmd("alice 1 oro"), Err(Error::JugadaIlegal(_))));
+  p.cmd("alice flor").unwrap();
+  // cantada la flor ya puede gritar
+  p.cmd("alice truco").unwrap();
+}
+
+#[test]
+fn sin_panics_test(){
+  let mut p = Partida::new(
+    20,
+    vec!["alice".to_string(), "ariana".to_string()],
+    vec!["bob".to_string(), "ben".to_string()],
+    true,
+  ).unwrap();
+
+  // un indice de jugador fuera de rango no es valido para ninguna jugada
+  let jugadas: Vec<Box<dyn IJugada>> = vec![
+    Box::new(TirarCarta{jid: 99, carta: p.ronda.muestra}),
+    Box::new(GritarTruco{jid: 99}),
+    Box::new(ResponderQuiero{jid: 99}),
+    Box::new(IrseAlMazo{jid: 99}),
+  ];
+  for j in jugadas.iter() {
+    let (pkts, ok) = j.ok(&p);
+    assert!(!ok);
+    assert_eq!(pkts.len(), 1);
+    let _ = j.hacer(&mut p);
+  }
+  assert_eq!(p.abandono("mallory").unwrap_err(), Error::JugadorInexistente("mallory".to_string()));
+  assert!(p.perspectiva("mallory").is_err());
+  assert!(p.perspectiva("alice").is_ok());
+
+  let pkts = p.abandono("bob").unwrap();
+  assert_eq!(pkts.len(), 1);
+  assert!(p.terminada());
+}
+
+// una ronda entre alice (mano) y bob en la que ya se jugaron las 3 manos
+// con los resultados dados
+fn ronda_jugada(resultados: [(Resultado, Option<usize>); 3]) -> Partida {
+  let mut p = Partida::new_with_seed(
+    20,
+    vec!["alice".to_string()],
+    vec!["bob".to_string()],
+    false,
+    0,
+  ).unwrap();
+  p.verbose = false;
+  for (mano, (resultado, ganador)) in p.ronda.manos.iter_mut().zip(resultados) {
+    mano.resultado = resultado;
+    mano.ganador = ganador;
+  }
+  p.ronda.mano_en_juego = NumMano::Tercera;
+  p
+}
+
+#[test]
+fn pardas_test(){
+  // gana alice la primera, bob la segunda y la tercera es parda: define
+  // la primera
+  let mut p = ronda_jugada([
+    (Resultado::GanoAzul, Some(0)),
+    (Resultado::GanoRojo, Some(1)),
+    (Resultado::Empardada, None),
+  ]);
+  assert!(p.evaluar_ronda().0);
+  assert_eq!(p.puntajes[&Equipo::Azul], 1);
+  assert_eq!(p.puntajes[&Equipo::Rojo], 0);
+
+  // parda la primera y la segunda: define la tercera
+  let mut p = ronda_jugada([
+    (Resultado::Empardada, None),
+    (Resultado::Empardada, None),
+    (Resultado::GanoRojo, Some(1)),
+  ]);
+  assert!(p.evaluar_ronda().0);
+  assert_eq!(p.puntajes[&Equipo::Rojo], 1);
+
+  // las tres pardas: gana el mano
+  let mut p = ronda_jugada([
+    (Resultado::Empardada, None),
+    (Resultado::Empardada, None),
+    (Resultado::Empardada, None),
+  ]);
+  assert!(p.evaluar_ronda().0);
+  assert_eq!(p.puntajes[&Equipo::Azul], 1);
+  assert_eq!(p.puntajes[&Equipo::Rojo], 0);
+}
+
+#[test]
+fn razon_ronda_ganada_test(){
+  let nueva = || Partida::new_with_seed(
+    20,
+    vec!["alice".to_string()],
+    vec!["bob".to_string()],
+    true,
+    0,
+  ).unwrap();
+  let razones = |pkts: Vec<enco::Packet>| pkts
+    .into_iter()
+    .filter_map(|pkt| match pkt.message.0 {
+      enco::Content::RondaGanada{autor, razon} => Some(format!("{} {:?}", autor, razon)),
+      _ => None,
+    })
+    .collect::<Vec<String>>();
+
+  let mut p = nueva();
+  p.cmd("alice truco").unwrap();
+  let pkts = p.cmd("bob no-quiero").unwrap();
+  assert_eq!(razones(pkts), vec!["alice TrucoNoQuerido"]);
+  assert_eq!(p.puntajes[&Equipo::Azul], 1);
+
+  let mut p = nueva();
+  let pkts = p.cmd("alice mazo").unwrap();
+  assert_eq!(razones(pkts), vec!["bob SeFueronAlMazo"]);
+  assert_eq!(p.puntajes[&Equipo::Rojo], 1);
+}
+
+#[test]
+fn historial_test(){
+  let mut p = Partida::new(
+    20,
+    vec!["alice".to_string(), "ariana".to_string()],
+    vec!["bob".to_string(), "ben".to_string()],
+    true,
+  ).unwrap();
+
+  let mut pkts: Vec<Packet> = Vec::new();
+  while !p.terminada() {
+    let a = random_action(&p, true);
+    pkts.append(&mut p.jugar(a.as_ref()));
+  }
+  assert!(p.historial.repartos.len() > 1);
+
+  // ida y vuelta por json (ej. a un archivo)
+  let json = serde_json::to_string(&p.historial).unwrap();
+  let historial: Historial = serde_json::from_str(&json).unwrap();
+  assert_eq!(historial, p.historial);
+
+  let (q, replay) = Partida::reproducir(&historial).unwrap();
+  assert_eq!(replay, pkts);
+  assert_eq!(q.historial, p.historial);
+  assert_eq!(
+    serde_json::to_value(&q).unwrap(),
+    serde_json::to_value(&p).unwrap()
+  );
+
+  // un historial adulterado no se puede reproducir
+  let mut adulterado = historial.clone();
+  adulterado.repartos[0].manojos[1] = adulterado.repartos[0].manojos[0];
+  assert!(Partida::reproducir(&adulterado).is_err());
+
+  // tampoco si lo adulterado es el reparto de una ronda posterior (no se
+  // reemplaza por uno al azar)
+  let mut adulterado = historial.clone();
+  adulterado.repartos[1].manojos[1] = adulterado.repartos[1].manojos[0];
+  assert_eq!(
+    Partida::reproducir(&adulterado).err(),
+    Some(Error::EstadoInvalido("el reparto tiene cartas repetidas".to_string()))
+  );
+}
+
+#[test]
+fn deshacer_test(){
+  let mut p = Partida::new(
+    20,
+    vec!["alice".to_string(), "ariana".to_string()],
+    vec!["bob".to_string(), "ben".to_string()],
+    false,
+  ).unwrap();
+  assert!(!p.deshacer());
+
+  // estados luego de cada jugada, cruzando rondas
+  let mut estados = vec![serde_json::to_value(&p).unwrap()];
+  while !p.terminada() {
+    let a = random_action(&p, true);
+    let _ = p.jugar(a.as_ref());
+    estados.push(serde_json::to_value(&p).unwrap());
+  }
+  let n = p.historial.jugadas.len();
+  assert!(p.historial.repartos.len() > 1);
+
+  for estado in estados.iter().rev().skip(1) {
+    assert!(p.deshacer());
+    assert_eq!(&serde_json::to_value(&p).unwrap(), estado);
+  }
+  assert!(!p.deshacer());
+  assert!(p.historial.jugadas.is_empty());
+  assert_eq!(p.puntajes[&Equipo::Azul] + p.puntajes[&Equipo::Rojo], 0);
+
+  for estado in estados.iter().skip(1) {
+    assert!(p.rehacer());
+    assert_eq!(&serde_json::to_value(&p).unwrap(), estado);
+  }
+  assert!(!p.rehacer());
+  assert_eq!(p.historial.jugadas.len(), n);
+
+  // una jugada nueva descarta lo que se podia rehacer
+  assert!(p.deshacer());
+  let a = random_action(&p, true);
+  let _ = p.jugar(a.as_ref());
+  assert!(!p.rehacer());
+  assert!(p.ronda.repartos_pendientes.is_empty());
+}
+
+#[test]
+fn reglas_test(){
+  let nueva = |reglas: Reglas, seed: u64| Partida::new_with_reglas_y_rng(
+    20,
+    vec!["alice".to_string()],
+    vec!["bob".to_string()],
+    reglas,
+    false,
+    Some(rand::SeedableRng::seed_from_u64(seed)),
+  );
+
+  // cualquier puntuacion
+  let p = Partida::new(25, vec!["alice".to_string()], vec!["bob".to_string()], false).unwrap();
+  assert_eq!(p.el_chico(), 12);
+
+  // el chico
+  let reglas = Reglas{el_chico: Some(5), ..Default::default()};
+  let mut p = nueva(reglas, 0).unwrap();
+  assert!(p.esta_en_malas(Equipo::Azul));
+  p.suma_puntos(Equipo::Azul, 5);
+  assert!(!p.esta_en_malas(Equipo::Azul));
+  let reglas = Reglas{el_chico: Some(20), ..Default::default()};
+  assert!(matches!(nueva(reglas, 0), Err(Error::ReglasInvalidas(_))));
+
+  // ambos con flor
+  let con_flor = |p: &Partida| p.ronda.manojos
+    .iter()
+    .all(|m| m.tiene_flor(&p.ronda.muestra).0);
+  let seed = (0..).find(|&s| con_flor(&nueva(Reglas::default(), s).unwrap())).unwrap();
+
+  let mut p = nueva(Reglas::default(), seed).unwrap();
+  assert_eq!(p.ronda.envite.jugadores_con_flor.len(), 2);
+  p.cmd("alice flor").unwrap();
+  assert!(p.cmd("bob contra-flor-al-resto").is_ok());
+
+  // sin contraflor al resto
+  let reglas = Reglas{contraflor_al_resto: false, ..Default::default()};
+  let mut p = nueva(reglas, seed).unwrap();
+  p.cmd("alice flor").unwrap();
+  assert!(matches!(p.cmd("bob contra-flor-al-resto"), Err(Error::JugadaIlegal(_))));
+  assert!(p.cmd("bob contra-flor").is_ok());
+
+  // sin flor: se juega como si nadie la tuviera
+  let reglas = Reglas{con_flor: false, ..Default::default()};
+  let mut p = nueva(reglas, seed).unwrap();
+  assert!(p.ronda.envite.jugadores_con_flor.is_empty());
+  assert!(matches!(p.cmd("alice flor"), Err(Error::JugadaIlegal(_))));
+  assert!(p.cmd("alice envido").is_ok());
+  while !p.terminada() {
+    assert!(p.ronda.envite.jugadores_con_flor.is_empty());
+    let a = random_action(&p, true);
+    assert!(![IJugadaId::JIdFlor, IJugadaId::JIdContraFlor, IJugadaId::JIdContraFlorAlResto]
+      .contains(&a.id()));
+    let _ = p.jugar(a.as_ref());
+  }
+}
+
+#[test]
+fn perspectiva_test(){
+  let nueva = |seed: u64| Partida::new_with_seed(
+    20,
+    vec!["alice".to_string(), "ariana".to_string()],
+    vec!["bob".to_string(), "ben".to_string()],
+    false,
+    seed,
+  ).unwrap();
+
+  // alguien del rojo tiene flor y todavia no la canto
+  let seed = (0..)
+    .find(|&s| {
+      let r = nueva(s).ronda;
+      r.envite.sin_cantar.iter().any(|&j| r.manojos[j].jugador.equipo == Equipo::Rojo)
+    })
+    .unwrap();
+  let mut p = nueva(seed);
+  let a = random_action(&p, false);
+  let _ = p.jugar(a.as_ref());
+
+  for jid in ["alice", "ariana", "bob", "ben"] {
+    let equipo = p.ronda.get_manojo(jid).unwrap().jugador.equipo;
+    let mut v = p.perspectiva(jid).unwrap();
+    let json = serde_json::to_string(&v).unwrap();
+
+    for (mix, m) in p.ronda.manojos
+      .iter()
+      .enumerate()
+      .filter(|(_, m)| m.jugador.equipo != equipo)
+    {
+      // ninguna carta no tirada de los contrarios sobrevive
+      for (ix, c) in m.cartas.iter().enumerate() {
+        let c = serde_json::to_string(c).unwrap();
+        assert_eq!(json.contains(&c), m.tiradas[ix]);
+      }
+      // ni quien tiene flor
+      assert!(!v.ronda.envite.sin_cantar.contains(&mix));
+      assert!(!v.ronda.envite.jugadores_con_flor.contains(&mix));
+    }
+
+    // ni nada de lo que se podria deducir el futuro o el pasado
+    assert!(v.ronda.rng.is_none());
+    assert!(v.ronda.repartos_pendientes.is_empty());
+    assert_eq!(v.historial, Historial::default());
+    assert!(!v.deshacer());
+  }
+}
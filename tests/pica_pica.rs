@@ -0,0 +1,114 @@
+use truco::*;
+
+mod common;
+use common::{nueva_con_reglas};
+
+fn nueva(pica_pica: bool, seed: u64) -> Partida {
+  nueva_con_reglas(
+    30,
+    &["alice", "ariana", "anna"],
+    &["bob", "ben", "bianca"],
+    Reglas{pica_pica, ..Default::default()},
+    seed,
+  )
+}
+
+#[test]
+fn duelos_test(){
+  let mut p = nueva(true, 0);
+  *p.puntajes.get_mut(&Equipo::Azul).unwrap() = 7;
+  assert!(p.toca_pica_pica());
+  p.armar_duelos().unwrap();
+  assert!(p.en_pica_pica());
+  assert_eq!(p.duelos.len(), 3);
+
+  // cada uno contra el de enfrente, con sus mismas cartas
+  for d in p.duelos.iter() {
+    let [a, b] = d.mixs;
+    assert_eq!(a.abs_diff(b), 3);
+    assert_eq!(d.partida.ronda.manojos[0].cartas, p.ronda.manojos[a].cartas);
+    assert_eq!(d.partida.ronda.manojos[1].cartas, p.ronda.manojos[b].cartas);
+    assert_eq!(d.partida.ronda.muestra, p.ronda.muestra);
+    assert_eq!(d.partida.puntajes, p.puntajes);
+  }
+
+  // los duelos no reparten: registran las cartas de la partida y sus
+  // rondas siguientes salen de la semilla de la partida
+  let mut r = nueva(true, 0);
+  *r.puntajes.get_mut(&Equipo::Azul).unwrap() = 7;
+  r.armar_duelos().unwrap();
+  for (d, e) in p.duelos.iter().zip(r.duelos.iter()) {
+    assert_eq!(d.partida.historial.repartos, vec![d.partida.ronda.get_reparto()]);
+    let (mut a, mut b) = (d.partida.clone(), e.partida.clone());
+    a.nueva_ronda().unwrap();
+    b.nueva_ronda().unwrap();
+    assert_eq!(a.ronda.get_reparto(), b.ronda.get_reparto());
+  }
+
+  // si no se puede armar un duelo no se arma ninguno
+  let mut q = nueva(true, 0);
+  *q.puntajes.get_mut(&Equipo::Azul).unwrap() = 7;
+  q.ronda.manojos[1].cartas[0] = q.ronda.manojos[4].cartas[0];
+  assert!(q.armar_duelos().is_err());
+  assert!(!q.en_pica_pica());
+
+  // el mano de la ronda es mano en su duelo
+  let el_mano = p.ronda.get_el_mano().jugador.id.clone();
+  let d = p.get_duelo(&el_mano).unwrap();
+  assert_eq!(d.ronda.get_el_mano().jugador.id, el_mano);
+
+  // los duelos se juegan en simultaneo: una jugada en uno no mueve los otros
+  for ix in 0..3 {
+    let jid = p.duelos[ix].partida.ronda.get_el_turno().jugador.id.clone();
+    let turnos = p.duelos.iter().map(|d| d.partida.ronda.turno).collect::<Vec<_>>();
+    let jugadas = chi(&p, p.ronda.get_mix(&jid).unwrap(), false);
+    assert!(!jugadas.is_empty());
+    let _ = p.jugar(jugadas[0].as_ref());
+    for (jx, d) in p.duelos.iter().enumerate() {
+      if jx != ix {
+        assert_eq!(d.partida.ronda.turno, turnos[jx]);
+      }
+    }
+  }
+}
+
+#[test]
+fn pica_pica_test(){
+  for seed in 0..10 {
+    let mut p = nueva(true, seed);
+    let mut rondas_pica_pica = 0;
+    let mut anterior = false;
+    while !p.terminada() {
+      let numero = p.ronda.numero;
+      let total = p.puntajes[&Equipo::Azul] + p.puntajes[&Equipo::Rojo];
+      let a = random_action(&p, true);
+      let _ = p.jugar(a.as_ref());
+      assert!(p.puntajes[&Equipo::Azul] + p.puntajes[&Equipo::Rojo] >= total);
+      // los puntos de un duelo llegan a los demas
+      for d in p.duelos.iter().filter(|d| !d.terminado) {
+        assert_eq!(d.partida.puntajes, p.puntajes);
+      }
+      if p.ronda.numero != numero && !p.terminada() {
+        // nunca 2 rondas de pica-pica seguidas
+        assert!(!(anterior && p.en_pica_pica()));
+        anterior = p.en_pica_pica();
+        if anterior {
+          rondas_pica_pica += 1;
+        }
+      }
+    }
+    assert!(rondas_pica_pica > 0);
+
+    // se puede reproducir
+    let (q, _) = Partida::reproducir(&p.historial).unwrap();
+    assert_eq!(q.puntajes, p.puntajes);
+  }
+
+  // sin la regla nunca hay duelos
+  let mut p = nueva(false, 0);
+  while !p.terminada() {
+    assert!(!p.en_pica_pica());
+    let a = random_action(&p, true);
+    let _ = p.jugar(a.as_ref());
+  }
+}
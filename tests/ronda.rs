@@ -16,20 +16,25 @@ fn ronda_manual_json_test(){
     envite: Envite {
       estado: EstadoEnvite::NoCantadoAun,
       puntaje: 0,
-      cantado_por: String::from(""),
+      cantado_por: None,
       jugadores_con_flor: Vec::new(),
       // alternativa
-      // pub jugadores_con_flor: Vec<String>,
+      // pub jugadores_con_flor: Vec<usize>,
       sin_cantar: Vec::new(),
     },
     truco: Truco{
-      cantado_por: String::from(""),
+      cantado_por: None,
       estado: EstadoTruco::NoCantado,
     },
     manojos: Vec::new(),
     muestra: Carta::new(4, "copa").unwrap(),
-    mixs: HashMap::new(),
+    mixs: Default::default(),
     manos: Default::default(),
+    numero: 0,
+    reglas: Reglas::default(),
+    rng: None,
+    repartos_pendientes: Default::default(),
+    reparto_rechazado: None,
   };
 
   let _json = serde_json::to_string(&r).unwrap();
@@ -51,4 +56,46 @@ fn new_ronda_json_test(){
   let r = Ronda::new(azules, rojos).unwrap();
   let _json = serde_json::to_string(&r).unwrap();
   // println!("the JSON is: {}", _json);
-}
\ No newline at end of file
+}
+#[test]
+fn nueva_ronda_manos_test(){
+  let mut p = Partida::new_with_seed(
+    20,
+    vec!["alice".to_string()],
+    vec!["bob".to_string()],
+    false,
+    0,
+  ).unwrap();
+  let c = p.ronda.manojos[0].cartas[0];
+  p.cmd(&format!("alice {} {}", c.valor, c.palo)).unwrap();
+  p.cmd("bob mazo").unwrap();
+  // arranca otra ronda: las manos de la anterior no quedan
+  assert_eq!(p.ronda.el_mano, 1);
+  for m in p.ronda.manos.iter() {
+    assert!(m.cartas_tiradas.is_empty());
+    assert_eq!(m.resultado, Resultado::Indeterminado);
+    assert_eq!(m.ganador, None);
+  }
+}
+
+#[test]
+fn partidas_al_azar_test(){
+  // partidas completas, yendose al mazo inclusive
+  for _ in 0..20 {
+    let mut p = Partida::new(
+      20,
+      vec!["alice".to_string(), "ariana".to_string()],
+      vec!["bob".to_string(), "ben".to_string()],
+      false,
+    ).unwrap();
+    while !p.terminada() {
+      let el_mano = p.ronda.el_mano;
+      let a = random_action(&p, true);
+      let _ = a.hacer(&mut p);
+      // en cada ronda nueva las manos arrancan vacias
+      if p.ronda.el_mano != el_mano && !p.terminada() {
+        assert!(p.ronda.manos.iter().all(|m| m.cartas_tiradas.is_empty()));
+      }
+    }
+  }
+}
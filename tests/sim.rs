@@ -0,0 +1,49 @@
+use truco::*;
+
+#[test]
+fn simular_test(){
+  let config = ConfigSim {
+    hilos: 1,
+    partidas: 50,
+    seed: 3,
+    ..Default::default()
+  };
+  let res = simular(&config).unwrap();
+  assert_eq!(res.partidas, 50);
+  assert_eq!(res.ganadas.values().sum::<usize>(), 50);
+  assert!(res.rondas >= 50);
+  assert!(res.paquetes > res.jugadas);
+  assert!(res.razones[&Razon::TrucoQuerido] > 0);
+
+  // mismo resultado con cualquier cantidad de hilos
+  for hilos in [2, 3, 8, 100] {
+    assert_eq!(simular(&ConfigSim{hilos, ..config.clone()}).unwrap(), res);
+  }
+
+  // otra semilla, otro resultado
+  assert_ne!(simular(&ConfigSim{seed: 4, ..config.clone()}).unwrap(), res);
+}
+
+#[test]
+fn simular_variantes_test(){
+  for (n, reglas) in [
+    (2, Reglas{con_flor: false, ..Default::default()}),
+    (3, Reglas{pica_pica: true, ..Default::default()}),
+  ] {
+    let config = ConfigSim {
+      hilos: 2,
+      partidas: 10,
+      jugadores_por_equipo: n,
+      reglas,
+      ..Default::default()
+    };
+    let res = simular(&config).unwrap();
+    assert_eq!(res.partidas, 10);
+    if !reglas.con_flor {
+      assert!(!res.razones.contains_key(&Razon::LaUnicaFlor));
+    }
+  }
+
+  let config = ConfigSim{jugadores_por_equipo: 4, ..Default::default()};
+  assert!(simular(&config).is_err());
+}
@@ -0,0 +1,125 @@
+use truco::*;
+
+fn nueva() -> Partida {
+  Partida::new(
+    20,
+    vec!["alice".to_string(), "ariana".to_string()],
+    vec!["bob".to_string(), "ben".to_string()],
+    false,
+  ).unwrap()
+}
+
+#[test]
+fn snapshot_test(){
+  let mut p = nueva();
+  // a mitad de partida
+  for _ in 0..15 {
+    if p.terminada() {
+      break
+    }
+    let a = random_action(&p, false);
+    let _ = p.jugar(a.as_ref());
+  }
+
+  let json = p.guardar().unwrap();
+  let mut q = Partida::cargar(&json).unwrap();
+  assert_eq!(q.ronda.mixs, p.ronda.mixs);
+  assert_eq!(q.ronda.envite.jugadores_con_flor, p.ronda.envite.jugadores_con_flor);
+  assert_eq!(
+    serde_json::to_value(&q).unwrap(),
+    serde_json::to_value(&p).unwrap()
+  );
+
+  // se puede seguir jugando sin problemas
+  while !q.terminada() {
+    let a = random_action(&q, true);
+    let _ = q.jugar(a.as_ref());
+  }
+}
+
+#[test]
+fn snapshot_flor_al_mazo_test(){
+  let mut p = nueva();
+  let c = |valor, palo| Carta::new(valor, palo).unwrap();
+  // alice y ariana tienen flor
+  p.ronda.set_reparto(&Reparto {
+    muestra: c(4, "copa"),
+    manojos: vec![
+      [c(1, "oro"), c(2, "oro"), c(3, "oro")],
+      [c(5, "basto"), c(6, "espada"), c(7, "copa")],
+      [c(1, "espada"), c(2, "espada"), c(3, "espada")],
+      [c(4, "basto"), c(5, "oro"), c(6, "copa")],
+    ],
+  }).unwrap();
+  p.ronda.cachear_flores(true);
+  assert_eq!(p.ronda.envite.jugadores_con_flor, vec![0, 2]);
+
+  p.cmd("alice mazo").unwrap();
+  assert_eq!(p.ronda.envite.jugadores_con_flor, vec![2]);
+
+  let q = Partida::cargar(&p.guardar().unwrap()).unwrap();
+  assert_eq!(q.ronda.envite.jugadores_con_flor, vec![2]);
+  assert_eq!(q.ronda.envite.sin_cantar, p.ronda.envite.sin_cantar);
+
+  // no se puede cargar con un jugador con flor que se fue al mazo
+  let mut v: serde_json::Value = serde_json::from_str(&p.guardar().unwrap()).unwrap();
+  v["partida"]["ronda"]["envite"]["jugadores_con_flor"] = serde_json::json!([0, 2]);
+  assert!(matches!(Partida::cargar(&v.to_string()), Err(Error::EstadoInvalido(_))));
+}
+
+#[test]
+fn snapshot_invalido_test(){
+  let p = nueva();
+  let snapshot = |p: &Partida| serde_json::to_value(Snapshot{
+    version: SNAPSHOT_VERSION,
+    partida: p.clone(),
+  }).unwrap().to_string();
+
+  let mut v: serde_json::Value = serde_json::from_str(&p.guardar().unwrap()).unwrap();
+  v["version"] = serde_json::json!(SNAPSHOT_VERSION + 1);
+  assert_eq!(
+    Partida::cargar(&v.to_string()).unwrap_err(),
+    Error::VersionIncompatible(SNAPSHOT_VERSION + 1)
+  );
+
+  assert!(matches!(Partida::cargar("{}"), Err(Error::EstadoInvalido(_))));
+
+  // cartas repetidas
+  let mut q = p.clone();
+  q.ronda.manojos[1].cartas[0] = q.ronda.manojos[0].cartas[0];
+  assert!(matches!(Partida::cargar(&snapshot(&q)), Err(Error::EstadoInvalido(_))));
+
+  // carta inexistente
+  let mut q = p.clone();
+  q.ronda.muestra.valor = 8;
+  assert!(matches!(Partida::cargar(&snapshot(&q)), Err(Error::CartaInvalida(_))));
+
+  // turno fuera de rango
+  let mut q = p.clone();
+  q.ronda.turno = 4;
+  assert!(matches!(Partida::cargar(&snapshot(&q)), Err(Error::EstadoInvalido(_))));
+
+  // una tirada que no figura en el manojo
+  let mut q = p.clone();
+  let tirada = q.ronda.manojos[0].tirar_carta(0, 0);
+  q.ronda.manos[0].agregar_tirada(tirada);
+  assert!(Partida::cargar(&snapshot(&q)).is_ok());
+  q.ronda.manojos[0].tiradas[0] = false;
+  assert!(matches!(Partida::cargar(&snapshot(&q)), Err(Error::EstadoInvalido(_))));
+
+  // menos jugadores
+  let mut q = p.clone();
+  q.ronda.manojos.pop();
+  assert_eq!(
+    Partida::cargar(&snapshot(&q)).unwrap_err(),
+    Error::CantidadDeJugadoresInvalida
+  );
+
+  // referencias a jugadores que no existen
+  let mut q = p.clone();
+  q.ronda.truco.cantado_por = Some(99);
+  assert_eq!(
+    Partida::cargar(&snapshot(&q)).unwrap_err(),
+    Error::JugadorInexistente("99".to_string())
+  );
+}
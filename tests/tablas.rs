@@ -0,0 +1,67 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use truco::*;
+
+fn baraja() -> Vec<Carta> {
+  let palos = ["basto", "copa", "espada", "oro"];
+  let valores = [1, 2, 3, 4, 5, 6, 7, 10, 11, 12];
+  palos
+    .iter()
+    .flat_map(|p| valores.iter().map(move |v| Carta::new(*v, p).unwrap()))
+    .collect()
+}
+
+#[test]
+fn tabla_cartas_test(){
+  inicializar_tablas();
+  for c in baraja() {
+    for m in baraja() {
+      assert_eq!(c.calc_poder(&m), c.calc_poder_sin_tabla(&m), "{} con {}", c, m);
+      assert_eq!(c.calc_puntaje(&m), c.calc_puntaje_sin_tabla(&m), "{} con {}", c, m);
+      assert_eq!(c.es_pieza(&m), c.es_pieza_sin_tabla(&m), "{} con {}", c, m);
+    }
+  }
+}
+
+#[test]
+fn todos_los_manojos_test(){
+  // cada conjunto de 3 cartas tiene su entrada en la tabla: si 2 manojos
+  // compartieran una, alguno daria distinto que sin tablas
+  let cartas = baraja();
+  let jugador = Jugador{id: "alice".to_string(), equipo: Equipo::Azul};
+  let muestras = [cartas[0], cartas[13], cartas[26], cartas[39]];
+  for c in 2..cartas.len() {
+    for b in 1..c {
+      for a in 0..b {
+        let m = Manojo::new(jugador.clone(), [cartas[c], cartas[a], cartas[b]]);
+        for muestra in muestras.iter().filter(|x| !m.cartas.contains(x)) {
+          assert_eq!(m.tiene_flor(muestra), m.tiene_flor_sin_tabla(muestra));
+          assert_eq!(m.calcular_envido(muestra), m.calcular_envido_sin_tabla(muestra));
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn tabla_manojos_test(){
+  let cartas = baraja();
+  let jugador = Jugador{id: "alice".to_string(), equipo: Equipo::Azul};
+  let mut rng = StdRng::seed_from_u64(0);
+  for _ in 0..20_000 {
+    let mut ids = [0; 3];
+    while ids[0] == ids[1] || ids[1] == ids[2] || ids[0] == ids[2] {
+      ids = [0; 3].map(|_| rng.gen_range(0..cartas.len()));
+    }
+    let m = Manojo::new(jugador.clone(), ids.map(|id| cartas[id]));
+    let muestra = cartas[rng.gen_range(0..cartas.len())];
+    assert_eq!(m.tiene_flor(&muestra), m.tiene_flor_sin_tabla(&muestra));
+    assert_eq!(m.calc_flor(&muestra), m.calc_flor_sin_tabla(&muestra));
+    assert_eq!(m.calcular_envido(&muestra), m.calcular_envido_sin_tabla(&muestra));
+  }
+
+  // con cartas que no son de la baraja se calcula sin tablas
+  let oculta = Carta{valor: 0, palo: Palo::Copa};
+  let m = Manojo::new(jugador, [oculta; 3]);
+  assert_eq!(m.tiene_flor(&cartas[0]), m.tiene_flor_sin_tabla(&cartas[0]));
+}
@@ -0,0 +1,68 @@
+use truco::*;
+use truco::enco::{codificar, codificar_observacion, TENSOR_LEN};
+
+mod common;
+use common::{ids, nueva};
+
+#[test]
+fn tensor_test(){
+  for seed in 0..10 {
+    let mut p = nueva(&["alice", "ariana"], &["bob", "ben"], seed);
+
+    while !p.terminada() {
+      for m in p.ronda.manojos.iter() {
+        let t = codificar(&p, &m.jugador.id).unwrap();
+        assert_eq!(t.len(), TENSOR_LEN);
+        assert!(t.iter().all(|x| (0.0..=1.0).contains(x)));
+
+        // sus cartas sin tirar, la muestra y siempre un solo estado
+        let sin_tirar = m.tiradas.iter().filter(|t| !**t).count();
+        assert_eq!(t[0..40].iter().sum::<f32>() as usize, sin_tirar);
+        for c in m.cartas.iter().zip(m.tiradas).filter(|(_, t)| !t).map(|(c, _)| c) {
+          assert_eq!(t[c.id()], 1.0);
+        }
+        assert_eq!(t[40 + p.ronda.muestra.id()], 1.0);
+        assert_eq!(t[320..328].iter().sum::<f32>(), 1.0);
+        assert_eq!(t[328..335].iter().sum::<f32>(), 1.0);
+
+        // su asiento relativo es el 0
+        let yo = p.ronda.mixs[&m.jugador.id];
+        assert_eq!(t[352 + yo], 1.0);
+        assert_eq!(t[340 + (p.ronda.turno + 4 - yo) % 4], 1.0);
+      }
+      let a = random_action(&p, true);
+      let _ = p.jugar(a.as_ref());
+    }
+  }
+
+  let p = Partida::new(20, ids(&["alice"]), ids(&["bob"]), false).unwrap();
+  assert!(codificar(&p, "nadie").is_err());
+}
+
+#[test]
+fn codificar_observacion_test(){
+  let p = nueva(&["alice"], &["bob"], 0);
+  let o = p.observar("alice").unwrap();
+  let json = serde_json::to_string(&o).unwrap();
+
+  // una observacion deserializada sin el puntaje de un equipo no rompe
+  let mut v: serde_json::Value = serde_json::from_str(&json).unwrap();
+  v["puntajes"].as_object_mut().unwrap().remove("rojo").unwrap();
+  let q: Observacion = serde_json::from_value(v).unwrap();
+  let t = codificar_observacion(&q).unwrap();
+  assert_eq!(t[335..337], [0.0, 0.0]);
+
+  // lo que no se puede codificar es un error
+  let mut q = o.clone();
+  q.jid = "nadie".to_string();
+  assert!(codificar_observacion(&q).is_err());
+  let mut q = o.clone();
+  q.turno = 2;
+  assert!(codificar_observacion(&q).is_err());
+  let mut q = o.clone();
+  q.manojos.pop();
+  assert!(codificar_observacion(&q).is_err());
+  let mut q = o;
+  q.muestra.valor = 9;
+  assert!(codificar_observacion(&q).is_err());
+}